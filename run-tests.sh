@@ -31,7 +31,9 @@ kill_pg() {
 }
 
 do_test() {
-    RUST_BACKTRACE=1 cargo test --features "serde diesel-uuid"
+    RUST_BACKTRACE=1 cargo test --features "serde diesel-uuid chrono time jiff sha2 blake3 heapless"
+    RUST_BACKTRACE=1 cargo test --no-default-features --features std
+    RUST_BACKTRACE=1 cargo test --no-default-features --features alloc
 }
 
 docker_container=$(run_pg)
@@ -0,0 +1,188 @@
+//! Base62 and base58, for IDs made only of letters and digits
+//!
+//! The `-` and `_` in URL-safe base64 stop double-click selection at word
+//! boundaries in most terminals and chat tools, and some systems reject
+//! them. Both of these encodings use 22 characters for every UUID, padding
+//! with their zero symbol on the left, so the length never depends on the
+//! value.
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use uuid::Uuid;
+
+use crate::errors::ParseError;
+use crate::UuidB64;
+
+/// Digits, then uppercase, then lowercase: ASCII order, so string order matches `Ord`
+const BASE62_ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+/// The Bitcoin alphabet, which leaves out `0`, `O`, `I` and `l`
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Both alphabets need 22 characters to hold 128 bits
+const ENCODED_LEN: usize = 22;
+
+fn encode(mut value: u128, alphabet: &[u8], f: &mut Formatter) -> FmtResult {
+    let base = alphabet.len() as u128;
+    let mut buf = [alphabet[0]; ENCODED_LEN];
+    for out in buf.iter_mut().rev() {
+        *out = alphabet[(value % base) as usize];
+        value /= base;
+    }
+    f.write_str(core::str::from_utf8(&buf).unwrap())
+}
+
+fn decode(s: &str, alphabet: &[u8]) -> Result<u128, ParseError> {
+    if s.len() != ENCODED_LEN {
+        return Err(ParseError::WrongLength {
+            expected: ENCODED_LEN,
+            found: s.len(),
+        });
+    }
+    let base = alphabet.len() as u128;
+    s.bytes().enumerate().try_fold(0u128, |value, (offset, c)| {
+        let digit = alphabet
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| ParseError::invalid_character(s, offset))?;
+        value
+            .checked_mul(base)
+            .and_then(|value| value.checked_add(digit as u128))
+            .ok_or(ParseError::OutOfRange)
+    })
+}
+
+/// Displays a `UuidB64` as base62, created by [`UuidB64::base62`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Base62(u128);
+
+impl Display for Base62 {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        encode(self.0, BASE62_ALPHABET, f)
+    }
+}
+
+/// Displays a `UuidB64` as base58, created by [`UuidB64::base58`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Base58(u128);
+
+impl Display for Base58 {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        encode(self.0, BASE58_ALPHABET, f)
+    }
+}
+
+impl<E> UuidB64<E> {
+    /// Display this ID as 22 characters of base62 (`0-9 A-Z a-z`)
+    ///
+    /// The alphabet is in ASCII order, so these strings sort the same way as
+    /// the UUIDs do.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.base62().to_string(), "5NXH9G03Qou9vbZ5Nk2VBO");
+    /// assert_eq!(UuidB64::parse_base62("5NXH9G03Qou9vbZ5Nk2VBO").unwrap(), id);
+    /// ```
+    pub fn base62(&self) -> Base62 {
+        Base62(self.uuid().as_u128())
+    }
+
+    /// Display this ID as 22 characters of base58, using the Bitcoin alphabet
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.base58().to_string(), "NpxGnbuQjiMf1wUsTiwTY1");
+    /// assert_eq!(UuidB64::parse_base58("NpxGnbuQjiMf1wUsTiwTY1").unwrap(), id);
+    /// ```
+    pub fn base58(&self) -> Base58 {
+        Base58(self.uuid().as_u128())
+    }
+}
+
+impl UuidB64 {
+    /// Parse the 22 character base62 form written by [`UuidB64::base62`]
+    pub fn parse_base62(s: &str) -> Result<UuidB64, ParseError> {
+        decode(s, BASE62_ALPHABET).map(|value| UuidB64::from(Uuid::from_u128(value)))
+    }
+
+    /// Parse the 22 character base58 form written by [`UuidB64::base58`]
+    pub fn parse_base58(s: &str) -> Result<UuidB64, ParseError> {
+        decode(s, BASE58_ALPHABET).map(|value| UuidB64::from(Uuid::from_u128(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_length_roundtrips() {
+        for id in [
+            UuidB64::from(Uuid::nil()),
+            UuidB64::from(Uuid::from_u128(1)),
+            UuidB64::from(Uuid::max()),
+            "sMHuhm9GTxuNi3hJ51287g".parse().unwrap(),
+        ] {
+            let base62 = id.base62().to_string();
+            assert_eq!(base62.len(), 22);
+            assert!(base62.bytes().all(|c| c.is_ascii_alphanumeric()));
+            assert_eq!(UuidB64::parse_base62(&base62).unwrap(), id);
+
+            let base58 = id.base58().to_string();
+            assert_eq!(base58.len(), 22);
+            assert!(base58.bytes().all(|c| c.is_ascii_alphanumeric()));
+            assert_eq!(UuidB64::parse_base58(&base58).unwrap(), id);
+        }
+        assert_eq!(
+            UuidB64::from(Uuid::from_u128(1)).base62().to_string(),
+            "0000000000000000000001"
+        );
+        assert_eq!(
+            UuidB64::from(Uuid::from_u128(1)).base58().to_string(),
+            "1111111111111111111112"
+        );
+        assert_eq!(
+            UuidB64::from(Uuid::max()).base62().to_string(),
+            "7n42DGM5Tflk9n8mt7Fhc7"
+        );
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn base62_sorts_like_ord() {
+        let mut ids: Vec<UuidB64> = (0..500).map(|_| UuidB64::new()).collect();
+        ids.sort();
+        let strings: Vec<String> = ids.iter().map(|id| id.base62().to_string()).collect();
+        let mut sorted = strings.clone();
+        sorted.sort();
+        assert_eq!(strings, sorted);
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(matches!(
+            UuidB64::parse_base62("000000000000000000001"),
+            Err(ParseError::WrongLength { found: 21, .. })
+        ));
+        assert!(matches!(
+            UuidB64::parse_base62("00000000000000000000001"),
+            Err(ParseError::WrongLength { found: 23, .. })
+        ));
+        assert!(matches!(
+            UuidB64::parse_base62("000000000000000000000-"),
+            Err(ParseError::InvalidCharacter {
+                offset: 21,
+                found: '-',
+                ..
+            })
+        ));
+        assert_eq!(
+            UuidB64::parse_base62("7n42DGM5Tflk9n8mt7Fhc8"),
+            Err(ParseError::OutOfRange)
+        );
+        // 0, O, I and l are not in the Bitcoin alphabet
+        assert!(UuidB64::parse_base58("0111111111111111111111").is_err());
+        assert!(UuidB64::parse_base58("l111111111111111111111").is_err());
+    }
+}
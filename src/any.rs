@@ -0,0 +1,247 @@
+//! Parsing every textual form of a UUID, for migrating from hyphenated IDs
+//!
+//! `FromStr` only accepts the encoding a `UuidB64` is declared with, which is
+//! what you want once everything has moved over. While clients still send
+//! the old forms, [`UuidB64::parse_any`] accepts all of them and reports
+//! which one it found, so you can log or count the stragglers.
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use uuid::Uuid;
+
+use crate::encoding::{self, Encoding};
+use crate::errors::ParseError;
+use crate::{B64Bytes, UuidB64};
+
+/// The textual form found by [`UuidB64::parse_any`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    /// 22 characters of URL-safe base64, the default `UuidB64` form
+    UrlSafeNoPad,
+    /// 24 characters of URL-safe base64, ending in `==`
+    UrlSafe,
+    /// 24 characters of standard base64, ending in `==`
+    Standard,
+    /// 22 characters of standard base64
+    StandardNoPad,
+    /// `b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee`
+    Hyphenated,
+    /// `b0c1ee866f464f1b8d8b7849e75dbcee`
+    Simple,
+    /// `{b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee}`
+    Braced,
+    /// `urn:uuid:b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee`
+    Urn,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(match self {
+            Format::UrlSafeNoPad => "URL-safe base64",
+            Format::UrlSafe => "padded URL-safe base64",
+            Format::Standard => "padded standard base64",
+            Format::StandardNoPad => "standard base64",
+            Format::Hyphenated => "hyphenated hex",
+            Format::Simple => "simple hex",
+            Format::Braced => "braced hex",
+            Format::Urn => "URN",
+        })
+    }
+}
+
+/// Decode base64 with the encoding `E`, forgetting which one it was
+fn decode<E: Encoding>(s: &str, format: Format) -> Result<(UuidB64, Format), ParseError> {
+    let bytes = s.parse::<B64Bytes<16, E>>()?;
+    Ok((UuidB64::from_b64_bytes(bytes.with_encoding()), format))
+}
+
+const HYPHENATED: &[u8; 36] = b"xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx";
+
+/// The error for 24 characters that do not end in `==`
+///
+/// Points at the first character that is not a symbol of either base64
+/// alphabet, or the padding, and otherwise the padding itself is missing.
+fn missing_padding(s: &str) -> ParseError {
+    let symbol = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+' | '/');
+    match s
+        .char_indices()
+        .find(|&(offset, c)| !(symbol(c) || (c == '=' && offset >= 22)))
+    {
+        Some((offset, _)) => ParseError::invalid_character(s, offset),
+        None => ParseError::InvalidPadding { source: None },
+    }
+}
+
+/// Check the hex forms character by character, so that errors can point at
+/// the first one that is wrong
+fn decode_hex(s: &str, format: Format) -> Result<(UuidB64, Format), ParseError> {
+    let (prefix, template, suffix): (&[u8], &[u8], &[u8]) = match format {
+        Format::Simple => (b"", &[b'x'; 32], b""),
+        Format::Hyphenated => (b"", HYPHENATED, b""),
+        Format::Braced => (b"{", HYPHENATED, b"}"),
+        _ => (b"urn:uuid:", HYPHENATED, b""),
+    };
+    let expected = prefix.iter().chain(template).chain(suffix);
+    for (offset, (&c, &want)) in s.as_bytes().iter().zip(expected).enumerate() {
+        let ok = match want {
+            b'x' => c.is_ascii_hexdigit(),
+            _ => c == want,
+        };
+        if !ok {
+            return Err(ParseError::invalid_character(s, offset));
+        }
+    }
+    let uuid = Uuid::try_parse(s).expect("the input matches the template");
+    Ok((UuidB64::from(uuid), format))
+}
+
+impl UuidB64 {
+    /// Parse any of the forms listed in [`Format`], and report which it was
+    ///
+    /// The forms have different lengths, or for the base64 forms different
+    /// alphabets, so there is never any doubt about which one was meant.
+    /// When a base64 string only uses characters both alphabets share it is
+    /// reported as URL-safe.
+    ///
+    /// ```rust
+    /// # use uuid_b64::{Format, UuidB64};
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(UuidB64::parse_any("sMHuhm9GTxuNi3hJ51287g").unwrap(), (id, Format::UrlSafeNoPad));
+    /// assert_eq!(
+    ///     UuidB64::parse_any("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap(),
+    ///     (id, Format::Hyphenated)
+    /// );
+    /// ```
+    ///
+    /// With the `serde` feature, `any_format` does the
+    /// same when deserializing.
+    pub fn parse_any(s: &str) -> Result<(UuidB64, Format), ParseError> {
+        // with a mix of alphabets, decoding as URL-safe reports the `+` or `/`
+        let standard = s.contains(['+', '/']) && !s.contains(['-', '_']);
+        match (s.len(), standard) {
+            (22, false) => decode::<encoding::UrlSafeNoPad>(s, Format::UrlSafeNoPad),
+            (22, true) => decode::<encoding::StandardNoPad>(s, Format::StandardNoPad),
+            (24, _) if !s.ends_with("==") => Err(missing_padding(s)),
+            (24, false) => decode::<encoding::UrlSafe>(s, Format::UrlSafe),
+            (24, true) => decode::<encoding::Standard>(s, Format::Standard),
+            (32, _) => decode_hex(s, Format::Simple),
+            (36, _) => decode_hex(s, Format::Hyphenated),
+            (38, _) => decode_hex(s, Format::Braced),
+            (45, _) => decode_hex(s, Format::Urn),
+            (found, _) => Err(ParseError::WrongLength {
+                expected: 22,
+                found,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_every_format() {
+        let uuid = Uuid::parse_str("fbc1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap();
+        let id = UuidB64::from(uuid);
+        for (s, format) in [
+            ("-8Huhm9GTxuNi3hJ51287g", Format::UrlSafeNoPad),
+            ("-8Huhm9GTxuNi3hJ51287g==", Format::UrlSafe),
+            ("+8Huhm9GTxuNi3hJ51287g==", Format::Standard),
+            ("+8Huhm9GTxuNi3hJ51287g", Format::StandardNoPad),
+            ("fbc1ee86-6f46-4f1b-8d8b-7849e75dbcee", Format::Hyphenated),
+            ("FBC1EE86-6F46-4F1B-8D8B-7849E75DBCEE", Format::Hyphenated),
+            ("fbc1ee866f464f1b8d8b7849e75dbcee", Format::Simple),
+            ("{fbc1ee86-6f46-4f1b-8d8b-7849e75dbcee}", Format::Braced),
+            ("urn:uuid:fbc1ee86-6f46-4f1b-8d8b-7849e75dbcee", Format::Urn),
+        ] {
+            assert_eq!(UuidB64::parse_any(s).unwrap(), (id, format), "{}", s);
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        assert!(matches!(
+            UuidB64::parse_any("fbc1ee86-6f46-4f1b_8d8b-7849e75dbcee"),
+            Err(ParseError::InvalidCharacter {
+                offset: 18,
+                found: '_',
+                ..
+            })
+        ));
+        assert!(matches!(
+            UuidB64::parse_any("urn:uuid:fbc1ee86-6f46-4f1b-8d8b-7849e75dbcex"),
+            Err(ParseError::InvalidCharacter {
+                offset: 44,
+                found: 'x',
+                ..
+            })
+        ));
+        assert!(matches!(
+            UuidB64::parse_any("-8Huhm9GTxuNi3hJ51287/"),
+            Err(ParseError::InvalidCharacter {
+                offset: 21,
+                found: '/',
+                ..
+            })
+        ));
+        assert!(matches!(
+            UuidB64::parse_any("-8Huhm9GTxuNi3hJ51287"),
+            Err(ParseError::WrongLength { found: 21, .. })
+        ));
+    }
+
+    #[test]
+    fn explains_24_characters_without_padding() {
+        assert_eq!(
+            UuidB64::parse_any("-8Huhm9GTxuNi3hJ51287gAA"),
+            Err(ParseError::InvalidPadding { source: None })
+        );
+        assert_eq!(
+            UuidB64::parse_any("-8Huhm9GTxuNi3hJ51287g=A"),
+            Err(ParseError::InvalidPadding { source: None })
+        );
+        assert!(matches!(
+            UuidB64::parse_any("-8Huhm9GTxuNi3hJ51287g=!"),
+            Err(ParseError::InvalidCharacter {
+                offset: 23,
+                found: '!',
+                ..
+            })
+        ));
+        assert!(matches!(
+            UuidB64::parse_any("-8Huhm9GTx!Ni3hJ51287gAA"),
+            Err(ParseError::InvalidCharacter {
+                offset: 10,
+                found: '!',
+                ..
+            })
+        ));
+        assert!(matches!(
+            UuidB64::parse_any("-8Huhm9GTxéNi3hJ51287gA"),
+            Err(ParseError::InvalidCharacter {
+                offset: 10,
+                found: 'é',
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        for bad in [
+            "",
+            // mixed alphabets
+            "-8Huhm9GTxuNi3hJ51287/",
+            "-8Huhm9GTxuNi3hJ51287/==",
+            // wrong lengths
+            "-8Huhm9GTxuNi3hJ51287",
+            "fbc1ee86-6f46-4f1b-8d8b-7849e75dbce",
+            // not hex
+            "gbc1ee866f464f1b8d8b7849e75dbcee",
+        ] {
+            assert!(UuidB64::parse_any(bad).is_err(), "{}", bad);
+        }
+    }
+}
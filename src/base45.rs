@@ -0,0 +1,190 @@
+//! RFC 9285 base45, for QR codes
+//!
+//! QR codes have an alphanumeric mode that stores two characters in 11 bits,
+//! but it only covers digits, uppercase letters and ` $%*+-./:`. Base64 needs
+//! lowercase letters, which pushes the whole code into byte mode at 8 bits a
+//! character. Base45 is made of exactly the alphanumeric mode's characters,
+//! so a UUID takes 24 characters and 132 bits instead of 176.
+
+use core::fmt::{self, Display, Formatter, Result as FmtResult, Write};
+
+use uuid::Uuid;
+
+use crate::errors::ParseError;
+use crate::UuidB64;
+
+/// Eight groups of three characters
+const ENCODED_LEN: usize = 24;
+
+/// The QR code alphanumeric character set, in the order RFC 9285 assigns values
+const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Write `bytes` as base45: every two bytes become three characters, and a
+/// trailing odd byte becomes two
+fn encode<W: Write>(bytes: &[u8], out: &mut W) -> fmt::Result {
+    for chunk in bytes.chunks(2) {
+        let (mut n, len) = match *chunk {
+            [a, b] => (usize::from(a) << 8 | usize::from(b), 3),
+            [a] => (usize::from(a), 2),
+            _ => unreachable!(),
+        };
+        for _ in 0..len {
+            out.write_char(char::from(ALPHABET[n % 45]))?;
+            n /= 45;
+        }
+    }
+    Ok(())
+}
+
+/// Decode base45 written by [`encode`] into `out`, returning the number of
+/// bytes written
+fn decode(s: &str, out: &mut [u8]) -> Result<usize, ParseError> {
+    let wrong_length = ParseError::WrongLength {
+        expected: out.len() / 2 * 3 + out.len() % 2 * 2,
+        found: s.len(),
+    };
+    let mut written = 0;
+    for (i, chunk) in s.as_bytes().chunks(3).enumerate() {
+        // front to back, so that the first bad byte starts a character
+        let mut digits = [0; 3];
+        for (j, &c) in chunk.iter().enumerate() {
+            digits[j] = ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or_else(|| ParseError::invalid_character(s, i * 3 + j))?;
+        }
+        let n = digits[..chunk.len()]
+            .iter()
+            .rev()
+            .fold(0, |n, &digit| n * 45 + digit);
+        let (decoded, len) = match chunk.len() {
+            3 if n <= 0xffff => ([(n >> 8) as u8, n as u8], 2),
+            2 if n <= 0xff => ([n as u8, 0], 1),
+            1 => return Err(wrong_length),
+            _ => return Err(ParseError::OutOfRange),
+        };
+        out.get_mut(written..written + len)
+            .ok_or_else(|| wrong_length.clone())?
+            .copy_from_slice(&decoded[..len]);
+        written += len;
+    }
+    Ok(written)
+}
+
+/// Displays a `UuidB64` as base45, created by [`UuidB64::base45`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Base45([u8; 16]);
+
+impl Display for Base45 {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        encode(&self.0, f)
+    }
+}
+
+impl<E> UuidB64<E> {
+    /// Display this ID as 24 characters of RFC 9285 base45
+    ///
+    /// The output can contain a space, so quote it or put it somewhere that
+    /// keeps whitespace, like a QR code.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.base45().to_string(), "OFM.6U13E10AA+HD9F9BT *N");
+    /// assert_eq!(UuidB64::parse_base45("OFM.6U13E10AA+HD9F9BT *N").unwrap(), id);
+    /// ```
+    pub fn base45(&self) -> Base45 {
+        Base45(*self.0.as_bytes())
+    }
+}
+
+impl UuidB64 {
+    /// Parse the 24 character base45 form written by [`UuidB64::base45`]
+    pub fn parse_base45(s: &str) -> Result<UuidB64, ParseError> {
+        if s.len() != ENCODED_LEN {
+            return Err(ParseError::WrongLength {
+                expected: ENCODED_LEN,
+                found: s.len(),
+            });
+        }
+        let mut bytes = [0; 16];
+        decode(s, &mut bytes)?;
+        Ok(UuidB64::from(Uuid::from_bytes(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc_9285_vectors() {
+        for (bytes, encoded) in [
+            (&b"AB"[..], "BB8"),
+            (b"Hello!!", "%69 VD92EX0"),
+            (b"base-45", "UJCLQE7W581"),
+            (b"ietf!", "QED8WEX0"),
+        ] {
+            let mut s = String::new();
+            encode(bytes, &mut s).unwrap();
+            assert_eq!(s, encoded);
+
+            let mut out = [0; 16];
+            let len = decode(encoded, &mut out).unwrap();
+            assert_eq!(&out[..len], bytes);
+        }
+        // 65536 does not fit in two bytes
+        assert_eq!(decode("GGW", &mut [0; 16]), Err(ParseError::OutOfRange));
+    }
+
+    #[test]
+    fn roundtrips() {
+        for id in [
+            UuidB64::from(Uuid::nil()),
+            UuidB64::from(Uuid::max()),
+            "sMHuhm9GTxuNi3hJ51287g".parse().unwrap(),
+        ] {
+            let base45 = id.base45().to_string();
+            assert_eq!(base45.len(), 24);
+            assert_eq!(UuidB64::parse_base45(&base45).unwrap(), id);
+        }
+        assert_eq!(
+            UuidB64::from(Uuid::max()).base45().to_string(),
+            "FGWFGWFGWFGWFGWFGWFGWFGW"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(matches!(
+            UuidB64::parse_base45("00000000000000000000000"),
+            Err(ParseError::WrongLength { found: 23, .. })
+        ));
+        assert!(matches!(
+            UuidB64::parse_base45("0000000000000000000000000"),
+            Err(ParseError::WrongLength { found: 25, .. })
+        ));
+        assert!(matches!(
+            UuidB64::parse_base45("00000000000000000000000a"),
+            Err(ParseError::InvalidCharacter {
+                offset: 23,
+                found: 'a',
+                ..
+            })
+        ));
+        // a multibyte character that starts inside a chunk
+        assert!(matches!(
+            UuidB64::parse_base45("0000000000000000000000é"),
+            Err(ParseError::InvalidCharacter {
+                offset: 22,
+                found: 'é',
+                ..
+            })
+        ));
+        // a group larger than 16 bits
+        assert_eq!(
+            UuidB64::parse_base45("000000000000000000000GGW"),
+            Err(ParseError::OutOfRange)
+        );
+    }
+}
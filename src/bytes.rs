@@ -0,0 +1,272 @@
+//! Fixed-size byte strings that display as base64, like `UuidB64` does
+//!
+//! Not every ID is a UUID: Snowflake IDs are 8 bytes, MongoDB ObjectIds are
+//! 12 and content hashes are often 32. [`B64Bytes`] gives any of them the
+//! same text form as `UuidB64`, following the same [`Encoding`] rules, and
+//! `UuidB64` itself is a thin wrapper around a `B64Bytes<16>`.
+//!
+//! ```rust
+//! # use uuid_b64::B64Bytes;
+//! let object_id = B64Bytes::<12>::from_bytes(*b"\x65\x0c\x1f\x7a\x9b\x3e\x42\x00\x12\x34\x56\x78");
+//! assert_eq!(object_id.to_string(), "ZQwfeps-QgASNFZ4");
+//! assert_eq!("ZQwfeps-QgASNFZ4".parse::<B64Bytes<12>>().unwrap(), object_id);
+//! ```
+
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use base64::display::Base64Display;
+use base64::engine::Config;
+use base64::Engine;
+
+use crate::encoding::{Encoding, UrlSafeNoPad};
+use crate::errors::ParseError;
+use crate::grouped;
+
+/// The largest `N` a [`B64Bytes`] can have
+pub const MAX_BYTES: usize = 64;
+
+/// The longest encoded form, `MAX_BYTES` with padding
+const MAX_ENCODED_LEN: usize = MAX_BYTES.div_ceil(3) * 4;
+
+/// `N` bytes that display as base64
+///
+/// The [`Encoding`] parameter works the same way as it does for `UuidB64`,
+/// and defaults to URL-safe with no padding. `N` can be at most
+/// [`MAX_BYTES`], so that the encoded form always fits in a [`B64Str`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct B64Bytes<const N: usize, E = UrlSafeNoPad>([u8; N], PhantomData<E>);
+
+impl<const N: usize, E> B64Bytes<N, E> {
+    /// Fails to compile any use of a `B64Bytes` that is too big
+    const FITS: () = assert!(N <= MAX_BYTES, "B64Bytes can hold at most MAX_BYTES bytes");
+
+    /// Wrap some bytes, usable in `const`s
+    pub const fn from_bytes(bytes: [u8; N]) -> B64Bytes<N, E> {
+        B64Bytes(bytes, PhantomData)
+    }
+
+    /// Borrow the raw bytes
+    pub const fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Copy the raw bytes out
+    pub const fn into_bytes(self) -> [u8; N] {
+        self.0
+    }
+
+    /// Change how these bytes are encoded
+    pub const fn with_encoding<F>(self) -> B64Bytes<N, F> {
+        B64Bytes(self.0, PhantomData)
+    }
+}
+
+impl<const N: usize, E: Encoding> B64Bytes<N, E> {
+    /// The length of the encoded form
+    pub fn encoded_len() -> usize {
+        let () = Self::FITS;
+        base64::encoded_len(N, E::ENGINE.config().encode_padding()).unwrap()
+    }
+
+    /// Encode into a stack-allocated [`B64Str`]
+    pub fn to_stack_str(&self) -> B64Str {
+        let len = Self::encoded_len();
+        let mut buf = [0; MAX_ENCODED_LEN];
+        E::ENGINE.encode_slice(self.0, &mut buf[..len]).unwrap();
+        B64Str {
+            buf,
+            len: len as u8,
+        }
+    }
+
+    /// Append the encoded form to `buffer`
+    #[cfg(feature = "alloc")]
+    pub fn to_buf(&self, buffer: &mut String) {
+        E::ENGINE.encode_string(self.0, buffer);
+    }
+
+    /// Decode `symbols`, which were taken from `s`
+    ///
+    /// `offsets` maps the index of each symbol to its byte offset in `s`, so
+    /// that errors point into the original input.
+    pub(crate) fn decode_symbols(
+        s: &str,
+        symbols: &[u8],
+        offsets: &[usize],
+    ) -> Result<Self, ParseError> {
+        let len = Self::encoded_len();
+        let wrong_length = ParseError::WrongLength {
+            expected: len,
+            found: s.len(),
+        };
+        if symbols.len() != len {
+            return Err(wrong_length);
+        }
+        let mut output = [0; N];
+        match E::ENGINE.decode_slice(symbols, &mut output) {
+            Ok(written) if written == N => Ok(B64Bytes::from_bytes(output)),
+            Ok(_) => Err(wrong_length),
+            Err(err) => Err(ParseError::from_base64(s, len, offsets, err)),
+        }
+    }
+}
+
+impl<const N: usize, E> From<[u8; N]> for B64Bytes<N, E> {
+    fn from(bytes: [u8; N]) -> Self {
+        B64Bytes::from_bytes(bytes)
+    }
+}
+
+impl<const N: usize, E> From<B64Bytes<N, E>> for [u8; N] {
+    fn from(bytes: B64Bytes<N, E>) -> Self {
+        bytes.0
+    }
+}
+
+/// Parse the encoded form, or the form written by `UuidB64::grouped`
+impl<const N: usize, E: Encoding> FromStr for B64Bytes<N, E> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let len = Self::encoded_len();
+        let mut ungrouped = [0; MAX_ENCODED_LEN];
+        let mut offsets = [0; MAX_ENCODED_LEN];
+        let symbols = match grouped::ungroup(s, len, &mut ungrouped, &mut offsets) {
+            Some(symbols) if s.len() != len => symbols,
+            _ => {
+                for (i, offset) in offsets.iter_mut().enumerate() {
+                    *offset = i;
+                }
+                s.as_bytes()
+            }
+        };
+        Self::decode_symbols(s, symbols, &offsets)
+    }
+}
+
+impl<const N: usize, E: Encoding> Debug for B64Bytes<N, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "B64Bytes({})", self)
+    }
+}
+
+impl<const N: usize, E: Encoding> Display for B64Bytes<N, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", Base64Display::new(&self.0, E::ENGINE))
+    }
+}
+
+/// The encoded form of a [`B64Bytes`], stored on the stack
+///
+/// Dereferences to `str`.
+#[derive(Copy, Clone)]
+pub struct B64Str {
+    buf: [u8; MAX_ENCODED_LEN],
+    len: u8,
+}
+
+impl Deref for B64Str {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        core::str::from_utf8(&self.buf[..usize::from(self.len)]).unwrap()
+    }
+}
+
+impl AsRef<str> for B64Str {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl PartialEq for B64Str {
+    fn eq(&self, other: &B64Str) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for B64Str {}
+
+impl PartialEq<str> for B64Str {
+    fn eq(&self, other: &str) -> bool {
+        &**self == other
+    }
+}
+
+impl PartialEq<&str> for B64Str {
+    fn eq(&self, other: &&str) -> bool {
+        &**self == *other
+    }
+}
+
+impl Debug for B64Str {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl Display for B64Str {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::Standard;
+
+    #[test]
+    fn roundtrips_every_size() {
+        fn check<const N: usize>() {
+            let mut bytes = [0; N];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = (i * 37 + 11) as u8;
+            }
+            let id = B64Bytes::<N>::from_bytes(bytes);
+            let s = id.to_string();
+            assert_eq!(s.len(), B64Bytes::<N>::encoded_len());
+            assert_eq!(id.to_stack_str(), s.as_str());
+            assert_eq!(s.parse::<B64Bytes<N>>().unwrap(), id);
+
+            let padded = id.with_encoding::<Standard>();
+            let s = padded.to_string();
+            assert_eq!(s.len() % 4, 0);
+            assert_eq!(padded.to_stack_str(), s.as_str());
+            assert_eq!(s.parse::<B64Bytes<N, Standard>>().unwrap(), padded);
+        }
+        check::<1>();
+        check::<8>();
+        check::<12>();
+        check::<16>();
+        check::<32>();
+        check::<MAX_BYTES>();
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        // one byte short and one byte long
+        assert!("AnEGWsECAA".parse::<B64Bytes<8>>().is_err());
+        assert!("AnEGWsECAAcA".parse::<B64Bytes<8>>().is_err());
+        assert!("AnEGWsECAAc".parse::<B64Bytes<12>>().is_err());
+    }
+
+    #[test]
+    fn known_values() {
+        let snowflake = B64Bytes::<8>::from(175928847299117063u64.to_be_bytes());
+        assert_eq!(snowflake.to_string(), "AnEGWsECAAc");
+        assert_eq!(format!("{:?}", snowflake), "B64Bytes(AnEGWsECAAc)");
+
+        let hash = B64Bytes::<32>::from([0xff; 32]);
+        assert_eq!(
+            hash.to_stack_str(),
+            "__________________________________________8"
+        );
+    }
+}
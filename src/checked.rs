@@ -0,0 +1,210 @@
+//! A base64 form with a check symbol, for IDs that people type in
+//!
+//! Nearly every 22 character string of base64 decodes to some UUID, so a
+//! typo in a hand-typed ID quietly turns into a different, valid ID. The
+//! checked form adds one symbol from the same alphabet, calculated with the
+//! [Damm algorithm][] over the 64 element finite field. The parser rejects
+//! every single-character substitution and every swap of two adjacent
+//! characters with [`ParseError::CheckSymbolMismatch`].
+//!
+//! [Damm algorithm]: https://en.wikipedia.org/wiki/Damm_algorithm
+
+use core::fmt::{Display, Formatter, Result as FmtResult, Write};
+
+use uuid::Uuid;
+
+use crate::encoding::{symbol_for, Encoding, UrlSafeNoPad, UUID_SYMBOLS};
+use crate::errors::ParseError;
+use crate::{B64Bytes, UuidB64};
+
+/// Multiply by `x` in GF(2^6), reducing by `x^6 + x + 1`
+fn times_x(value: u8) -> u8 {
+    let shifted = (value << 1) & 0x3f;
+    if value & 0x20 == 0 {
+        shifted
+    } else {
+        shifted ^ 0x03
+    }
+}
+
+/// One step of the Damm algorithm, using the quasigroup `a * b = x·a + b`
+///
+/// Because `x + 1` is not zero, `(c * a) * b == (c * b) * a` only when
+/// `a == b`, which is what makes every adjacent transposition detectable.
+fn step(interim: u8, digit: u8) -> u8 {
+    times_x(interim) ^ digit
+}
+
+/// The check symbol value for a sequence of symbol values
+fn check_value(digits: &[u8]) -> u8 {
+    times_x(
+        digits
+            .iter()
+            .fold(0, |interim, &digit| step(interim, digit)),
+    )
+}
+
+/// The values of the symbols that encode `uuid`
+///
+/// `low` holds the last symbol's four unused bits, which are zero for any
+/// string an encoder wrote.
+fn symbol_values(uuid: u128, low: u8) -> [u8; UUID_SYMBOLS] {
+    let mut values = [0; UUID_SYMBOLS];
+    for (i, value) in values.iter_mut().enumerate().take(UUID_SYMBOLS - 1) {
+        *value = (uuid >> (122 - 6 * i)) as u8 & 0x3f;
+    }
+    values[UUID_SYMBOLS - 1] = (uuid as u8 & 0x03) << 4 | low;
+    values
+}
+
+/// Displays a `UuidB64` with a trailing check symbol, created by
+/// [`UuidB64::checked`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Checked<E: Encoding = UrlSafeNoPad>(UuidB64<E>);
+
+impl<E: Encoding> Display for Checked<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let encoded = self.0.as_b64_bytes().to_stack_str();
+        let check = check_value(&symbol_values(self.0.uuid().as_u128(), 0));
+        f.write_str(&encoded[..UUID_SYMBOLS])?;
+        f.write_char(char::from(symbol_for::<E>(check)))
+    }
+}
+
+impl<E: Encoding> UuidB64<E> {
+    /// Display this ID as 22 characters of base64 followed by a check symbol
+    ///
+    /// Padding is always left off, so the checked form is 23 characters
+    /// whichever encoding is used.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.checked().to_string(), "sMHuhm9GTxuNi3hJ51287gH");
+    ///
+    /// assert_eq!(UuidB64::parse_checked("sMHuhm9GTxuNi3hJ51287gH").unwrap(), id);
+    ///
+    /// // a typo, and two swapped characters
+    /// assert!(<UuidB64>::parse_checked("sMHuhm9GTxuNl3hJ51287gH").is_err());
+    /// assert!(<UuidB64>::parse_checked("sMHuhm9GTxuN3ihJ51287gH").is_err());
+    /// ```
+    pub fn checked(&self) -> Checked<E> {
+        Checked(*self)
+    }
+
+    /// Parse the 23 character form written by [`UuidB64::checked`]
+    ///
+    /// If every character is in the alphabet but the check symbol does not
+    /// match, this returns [`ParseError::CheckSymbolMismatch`].
+    pub fn parse_checked(s: &str) -> Result<Self, ParseError> {
+        if s.len() != UUID_SYMBOLS + 1 {
+            return Err(ParseError::WrongLength {
+                expected: UUID_SYMBOLS + 1,
+                found: s.len(),
+            });
+        }
+        // two more symbols make a whole number of bytes, so the last
+        // symbol's unused bits are decoded rather than rejected until the
+        // check symbol has been compared
+        let mut symbols = [symbol_for::<E>(0); UUID_SYMBOLS + 2];
+        symbols[..UUID_SYMBOLS].copy_from_slice(&s.as_bytes()[..UUID_SYMBOLS]);
+        let offsets: [usize; UUID_SYMBOLS + 2] = core::array::from_fn(|i| i);
+        let bytes = B64Bytes::<18, E>::decode_symbols(s, &symbols, &offsets)?.into_bytes();
+        let check = [s.as_bytes()[UUID_SYMBOLS]; 4];
+        let check = B64Bytes::<3, E>::decode_symbols(s, &check, &[UUID_SYMBOLS; 4])?;
+
+        let (uuid, rest) = bytes.split_at(16);
+        let uuid = u128::from_be_bytes(uuid.try_into().unwrap());
+        let low = rest[0] >> 4;
+        if check_value(&symbol_values(uuid, low)) != check.as_bytes()[0] >> 2 {
+            return Err(ParseError::CheckSymbolMismatch);
+        }
+        if low != 0 {
+            return Err(ParseError::NonCanonicalTrailingBits {
+                offset: UUID_SYMBOLS - 1,
+                source: None,
+            });
+        }
+        Ok(UuidB64::from_uuid(Uuid::from_u128(uuid)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::encoding::{Sortable, Standard};
+
+    #[test]
+    fn roundtrips() {
+        for id in [
+            UuidB64::from(Uuid::nil()),
+            UuidB64::from(Uuid::max()),
+            "sMHuhm9GTxuNi3hJ51287g".parse().unwrap(),
+        ] {
+            let checked = id.checked().to_string();
+            assert_eq!(checked.len(), 23);
+            assert!(checked.starts_with(&id.to_string()));
+            assert_eq!(UuidB64::parse_checked(&checked).unwrap(), id);
+
+            let sortable = id.with_encoding::<Sortable>();
+            let checked = sortable.checked().to_string();
+            assert_eq!(UuidB64::parse_checked(&checked).unwrap(), sortable);
+
+            // padding is left off
+            let standard = id.with_encoding::<Standard>();
+            let checked = standard.checked().to_string();
+            assert_eq!(checked.len(), 23);
+            assert_eq!(UuidB64::parse_checked(&checked).unwrap(), standard);
+        }
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn detects_substitutions_and_transpositions() {
+        let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+        for _ in 0..20 {
+            let checked = UuidB64::new().checked().to_string().into_bytes();
+            for i in 0..checked.len() {
+                for &c in alphabet.iter().filter(|&&c| c != checked[i]) {
+                    let mut typo = checked.clone();
+                    typo[i] = c;
+                    let typo = String::from_utf8(typo).unwrap();
+                    match <UuidB64>::parse_checked(&typo) {
+                        Err(ParseError::CheckSymbolMismatch) => {}
+                        other => panic!("{} gave {:?}", typo, other),
+                    }
+                }
+                if i + 1 < checked.len() && checked[i] != checked[i + 1] {
+                    let mut swapped = checked.clone();
+                    swapped.swap(i, i + 1);
+                    let swapped = String::from_utf8(swapped).unwrap();
+                    match <UuidB64>::parse_checked(&swapped) {
+                        Err(ParseError::CheckSymbolMismatch) => {}
+                        other => panic!("{} gave {:?}", swapped, other),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        for bad in ["sMHuhm9GTxuNi3hJ51287g", "sMHuhm9GTxuNi3hJ51287gHH"] {
+            assert!(matches!(
+                <UuidB64>::parse_checked(bad),
+                Err(ParseError::WrongLength { expected: 23, .. })
+            ));
+        }
+        for bad in ["sMHuhm9GTxuNi3hJ51287g=", "sMHuhm9GTxuNi3hJ51287+H"] {
+            assert!(matches!(
+                <UuidB64>::parse_checked(bad),
+                Err(ParseError::InvalidCharacter {
+                    offset: 22 | 21,
+                    ..
+                })
+            ));
+        }
+    }
+}
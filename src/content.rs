@@ -0,0 +1,174 @@
+//! IDs derived from a cryptographic hash of some content
+//!
+//! The first 128 bits of the digest are used as the UUID with the version and
+//! variant bits overwritten to mark it as v8, following the SHA-256 example
+//! in RFC 9562 appendix B.2. That leaves 122 bits of the digest in the ID.
+
+use core::hash::{Hash, Hasher};
+
+use uuid::Uuid;
+
+use crate::UuidB64;
+
+/// A hash function that content-addressed IDs can be built from
+trait ContentDigest: Clone {
+    fn update(&mut self, bytes: &[u8]);
+    fn first_16(self) -> [u8; 16];
+}
+
+#[cfg(feature = "sha2")]
+impl ContentDigest for sha2::Sha256 {
+    fn update(&mut self, bytes: &[u8]) {
+        sha2::Digest::update(self, bytes);
+    }
+
+    fn first_16(self) -> [u8; 16] {
+        let digest = sha2::Digest::finalize(self);
+        let mut out = [0; 16];
+        out.copy_from_slice(&digest[..16]);
+        out
+    }
+}
+
+#[cfg(feature = "blake3")]
+impl ContentDigest for blake3::Hasher {
+    fn update(&mut self, bytes: &[u8]) {
+        blake3::Hasher::update(self, bytes);
+    }
+
+    fn first_16(self) -> [u8; 16] {
+        let mut out = [0; 16];
+        out.copy_from_slice(&self.finalize().as_bytes()[..16]);
+        out
+    }
+}
+
+/// Lets a `Hash` impl feed its bytes into a cryptographic digest
+struct DigestHasher<D>(D);
+
+impl<D: ContentDigest> Hasher for DigestHasher<D> {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().first_16();
+        u64::from_be_bytes([
+            digest[0], digest[1], digest[2], digest[3], digest[4], digest[5], digest[6], digest[7],
+        ])
+    }
+}
+
+fn from_digest<D: ContentDigest>(digest: D) -> UuidB64 {
+    UuidB64::from_uuid(Uuid::new_v8(digest.first_16()))
+}
+
+/// The v8 ID of the SHA-256 digest of `parts`, one after another
+#[cfg(feature = "sha2")]
+pub(crate) fn sha256_of(parts: &[&[u8]]) -> UuidB64 {
+    let mut digest = sha2::Sha256::default();
+    for part in parts {
+        digest.update(part);
+    }
+    from_digest(digest)
+}
+
+fn from_hashed<D: ContentDigest, T: Hash + ?Sized>(digest: D, value: &T) -> UuidB64 {
+    let mut hasher = DigestHasher(digest);
+    value.hash(&mut hasher);
+    from_digest(hasher.0)
+}
+
+impl UuidB64 {
+    /// Create a v8 ID from the SHA-256 digest of `content`
+    ///
+    /// The same content always produces the same ID, which makes these useful
+    /// as idempotency keys or for deduplicating blobs.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::from_sha256(b"hello world");
+    /// assert_eq!(id.uuid().to_string(), "b94d27b9-934d-8e08-a52e-52d7da7dabfa");
+    /// assert_eq!(id, UuidB64::from_sha256(b"hello world"));
+    /// ```
+    #[cfg(feature = "sha2")]
+    pub fn from_sha256(content: impl AsRef<[u8]>) -> UuidB64 {
+        sha256_of(&[content.as_ref()])
+    }
+
+    /// Create a v8 ID from the SHA-256 digest of the bytes `value` feeds to a [`Hasher`]
+    ///
+    /// `Hash` impls are only stable for a given compiler and platform (for
+    /// example `usize` is written with the platform's width and endianness),
+    /// so prefer [`from_sha256`](UuidB64::from_sha256) with an explicit
+    /// serialization for IDs that are persisted or shared between systems.
+    #[cfg(feature = "sha2")]
+    pub fn from_sha256_hash<T: Hash + ?Sized>(value: &T) -> UuidB64 {
+        from_hashed(sha2::Sha256::default(), value)
+    }
+
+    /// Create a v8 ID from the BLAKE3 digest of `content`
+    ///
+    /// The same content always produces the same ID, which makes these useful
+    /// as idempotency keys or for deduplicating blobs.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::from_blake3(b"hello world");
+    /// assert_eq!(id, UuidB64::from_blake3(b"hello world"));
+    /// assert_ne!(id, UuidB64::from_blake3(b"hello world!"));
+    /// ```
+    #[cfg(feature = "blake3")]
+    pub fn from_blake3(content: impl AsRef<[u8]>) -> UuidB64 {
+        let mut digest = blake3::Hasher::new();
+        digest.update(content.as_ref());
+        from_digest(digest)
+    }
+
+    /// Create a v8 ID from the BLAKE3 digest of the bytes `value` feeds to a [`Hasher`]
+    ///
+    /// See [`from_sha256_hash`](UuidB64::from_sha256_hash) for the caveats.
+    #[cfg(feature = "blake3")]
+    pub fn from_blake3_hash<T: Hash + ?Sized>(value: &T) -> UuidB64 {
+        from_hashed(blake3::Hasher::new(), value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Version;
+
+    use super::*;
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn sha256_ids() {
+        // SHA-256("") starts e3b0c442 98fc1c14 9afbf4c8 996fb924
+        let id = UuidB64::from_sha256(b"");
+        assert_eq!(
+            id.uuid().to_string(),
+            "e3b0c442-98fc-8c14-9afb-f4c8996fb924"
+        );
+        assert_eq!(id.uuid().get_version(), Some(Version::Custom));
+
+        let hashed = UuidB64::from_sha256_hash(&("invoice", 17u32));
+        assert_eq!(hashed, UuidB64::from_sha256_hash(&("invoice", 17u32)));
+        assert_ne!(hashed, UuidB64::from_sha256_hash(&("invoice", 18u32)));
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3_ids() {
+        // BLAKE3("") starts af1349b9 f5f9a1a6 a0404dea 36dcc949
+        let id = UuidB64::from_blake3(b"");
+        assert_eq!(
+            id.uuid().to_string(),
+            "af1349b9-f5f9-81a6-a040-4dea36dcc949"
+        );
+        assert_eq!(id.uuid().get_version(), Some(Version::Custom));
+
+        let hashed = UuidB64::from_blake3_hash("upload.bin");
+        assert_eq!(hashed, UuidB64::from_blake3_hash("upload.bin"));
+        assert_ne!(hashed, UuidB64::from_blake3("upload.bin"));
+    }
+}
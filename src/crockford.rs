@@ -0,0 +1,232 @@
+//! Crockford base32, a case-insensitive form for IDs people read aloud
+//!
+//! See <https://www.crockford.com/base32.html>. A UUID takes 26 characters,
+//! the first of which is always `0`-`7` since 26 characters hold 130 bits.
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use uuid::Uuid;
+
+use crate::errors::ParseError;
+use crate::UuidB64;
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// The five extra symbols that are only used for the check symbol
+const CHECK_ALPHABET: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+const ENCODED_LEN: usize = 26;
+
+fn check_value(value: u128) -> usize {
+    (value % 37) as usize
+}
+
+/// Displays a `UuidB64` as Crockford base32, created by [`UuidB64::crockford`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Crockford {
+    value: u128,
+    check_symbol: bool,
+}
+
+impl Crockford {
+    /// Append the mod-37 check symbol, which catches most typos
+    pub fn with_check_symbol(mut self) -> Crockford {
+        self.check_symbol = true;
+        self
+    }
+}
+
+impl Display for Crockford {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let mut buf = [0; ENCODED_LEN + 1];
+        for (i, out) in buf[..ENCODED_LEN].iter_mut().enumerate() {
+            let shift = 5 * (ENCODED_LEN - 1 - i);
+            *out = ALPHABET[(self.value >> shift) as usize & 0x1f];
+        }
+        let len = if self.check_symbol {
+            buf[ENCODED_LEN] = CHECK_ALPHABET[check_value(self.value)];
+            ENCODED_LEN + 1
+        } else {
+            ENCODED_LEN
+        };
+        f.write_str(core::str::from_utf8(&buf[..len]).unwrap())
+    }
+}
+
+/// The value of a Crockford symbol, accepting lowercase and the aliases for 0 and 1
+fn decode_symbol(c: u8) -> Option<u8> {
+    let value = match c.to_ascii_uppercase() {
+        b'0'..=b'9' => c - b'0',
+        b'O' => 0,
+        b'I' | b'L' => 1,
+        b'A'..=b'H' => c.to_ascii_uppercase() - b'A' + 10,
+        b'J' | b'K' => c.to_ascii_uppercase() - b'J' + 18,
+        b'M' | b'N' => c.to_ascii_uppercase() - b'M' + 20,
+        b'P'..=b'T' => c.to_ascii_uppercase() - b'P' + 22,
+        b'V'..=b'Z' => c.to_ascii_uppercase() - b'V' + 27,
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn decode_check_symbol(c: u8) -> Option<usize> {
+    match c {
+        b'*' => Some(32),
+        b'~' => Some(33),
+        b'$' => Some(34),
+        b'=' => Some(35),
+        b'U' | b'u' => Some(36),
+        _ => decode_symbol(c).map(usize::from),
+    }
+}
+
+impl<E> UuidB64<E> {
+    /// Display this ID as 26 characters of Crockford base32
+    ///
+    /// Crockford base32 is case-insensitive and leaves out the letters that
+    /// are easily confused with digits, which makes it a good fit for IDs
+    /// that are read over the phone or used as file names on case-insensitive
+    /// file systems.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.crockford().to_string(), "5GR7Q8CVT69WDRV2VR97KNVF7E");
+    /// assert_eq!(id.crockford().with_check_symbol().to_string(), "5GR7Q8CVT69WDRV2VR97KNVF7EU");
+    ///
+    /// let parsed = UuidB64::parse_crockford("5gr7q8cvt69wdrv2vr97knvf7e").unwrap();
+    /// assert_eq!(parsed, id);
+    /// ```
+    pub fn crockford(&self) -> Crockford {
+        Crockford {
+            value: self.uuid().as_u128(),
+            check_symbol: false,
+        }
+    }
+}
+
+impl UuidB64 {
+    /// Parse Crockford base32, as written by [`UuidB64::crockford`]
+    ///
+    /// Parsing ignores case and hyphens, and reads `I` and `L` as `1` and `O`
+    /// as `0`. A 27th character is taken to be a check symbol and must match
+    /// the rest of the input.
+    pub fn parse_crockford(s: &str) -> Result<UuidB64, ParseError> {
+        let found = s.bytes().filter(|&c| c != b'-').count();
+        if found != ENCODED_LEN && found != ENCODED_LEN + 1 {
+            return Err(ParseError::WrongLength {
+                expected: ENCODED_LEN,
+                found,
+            });
+        }
+        let mut symbols = s.bytes().enumerate().filter(|&(_, c)| c != b'-');
+        let mut value: u128 = 0;
+        for (i, (offset, c)) in symbols.by_ref().take(ENCODED_LEN).enumerate() {
+            let symbol =
+                decode_symbol(c).ok_or_else(|| ParseError::invalid_character(s, offset))?;
+            if i == 0 && symbol > 7 {
+                return Err(ParseError::OutOfRange);
+            }
+            value = (value << 5) | u128::from(symbol);
+        }
+        if let Some((offset, c)) = symbols.next() {
+            let check =
+                decode_check_symbol(c).ok_or_else(|| ParseError::invalid_character(s, offset))?;
+            if check != check_value(value) {
+                return Err(ParseError::CheckSymbolMismatch);
+            }
+        }
+        Ok(UuidB64::from(Uuid::from_u128(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn roundtrips() {
+        for id in [
+            UuidB64::from(Uuid::nil()),
+            UuidB64::from(Uuid::max()),
+            "sMHuhm9GTxuNi3hJ51287g".parse().unwrap(),
+            "AYvP5WgAcSOEVniavN7wEg".parse().unwrap(),
+        ] {
+            let plain = id.crockford().to_string();
+            assert_eq!(plain.len(), 26);
+            assert_eq!(UuidB64::parse_crockford(&plain).unwrap(), id);
+
+            let checked = id.crockford().with_check_symbol().to_string();
+            assert_eq!(checked.len(), 27);
+            assert_eq!(UuidB64::parse_crockford(&checked).unwrap(), id);
+        }
+        assert_eq!(
+            UuidB64::from(Uuid::max()).crockford().to_string(),
+            "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+        );
+    }
+
+    #[test]
+    fn forgiving_parser() {
+        let id = UuidB64::parse_crockford("01234567890ABCDEFGHJKMNPQR").unwrap();
+        for alias in [
+            "o1234567890abcdefghjkmnpqr",
+            "OI234567890ABCDEFGHJKMNPQR",
+            "0l234567890ABCDEFGHJKMNPQR",
+            "01234-56789-0ABCD-EFGHJ-KMNPQR",
+        ] {
+            assert_eq!(UuidB64::parse_crockford(alias).unwrap(), id, "{}", alias);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        for bad in [
+            "",
+            "0123456789ABCDEFGHJKMNPQ",
+            "0123456789ABCDEFGHJKMNPQRS00",
+        ] {
+            assert!(
+                matches!(
+                    UuidB64::parse_crockford(bad),
+                    Err(ParseError::WrongLength { .. })
+                ),
+                "{}",
+                bad
+            );
+        }
+        assert_eq!(
+            UuidB64::parse_crockford("8ZZZZZZZZZZZZZZZZZZZZZZZZZ"),
+            Err(ParseError::OutOfRange)
+        );
+        // U is not a symbol, only a check symbol
+        assert!(matches!(
+            UuidB64::parse_crockford("0123456789ABCDEFGHJKMNPQRU0"),
+            Err(ParseError::InvalidCharacter {
+                offset: 25,
+                found: 'U',
+                ..
+            })
+        ));
+        assert!(matches!(
+            UuidB64::parse_crockford("01234-56789ABCDEFGHJKMNPQ!R"),
+            Err(ParseError::InvalidCharacter {
+                offset: 25,
+                found: '!',
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn check_symbol_catches_typos() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let checked = id.crockford().with_check_symbol().to_string();
+        let mut typo = checked.into_bytes();
+        typo[5] = if typo[5] == b'7' { b'8' } else { b'7' };
+        assert!(matches!(
+            UuidB64::parse_crockford(core::str::from_utf8(&typo).unwrap()),
+            Err(ParseError::CheckSymbolMismatch)
+        ));
+    }
+}
@@ -0,0 +1,18 @@
+use defmt::{Format, Formatter};
+
+use crate::encoding::Encoding;
+use crate::{B64Bytes, UuidB64};
+
+/// Logs the same base64 form as `Display`, from a stack buffer
+impl<const N: usize, E: Encoding> Format for B64Bytes<N, E> {
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "{=str}", &*self.to_stack_str());
+    }
+}
+
+/// Logs the same base64 form as `Display`, from a stack buffer
+impl<E: Encoding> Format for UuidB64<E> {
+    fn format(&self, f: Formatter) {
+        self.as_b64_bytes().format(f);
+    }
+}
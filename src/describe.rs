@@ -0,0 +1,173 @@
+//! Human-readable reports on where a `UuidB64` came from
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use inlinable_string::inline_string::InlineString;
+use uuid::{Variant, Version};
+
+use crate::{UuidB64, UuidTimestamp};
+
+/// Everything that can be read out of a `UuidB64` without any context
+///
+/// Created by [`UuidB64::describe`]. The `Display` impl renders a multi-line
+/// report that is meant to be read by people:
+///
+/// ```rust
+/// # use uuid_b64::UuidB64;
+/// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// assert_eq!(
+///     id.describe().to_string(),
+///     "\
+/// Base64:     sMHuhm9GTxuNi3hJ51287g
+/// Hyphenated: b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee
+/// Version:    4 (random)
+/// Variant:    RFC 4122
+/// "
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Description {
+    /// The ID that was described
+    pub id: UuidB64,
+    /// The version, `None` if the version field holds an unassigned value
+    pub version: Option<Version>,
+    /// The raw value of the version field
+    pub version_num: usize,
+    /// The variant (layout) of the UUID
+    pub variant: Variant,
+    /// The creation time, for v1, v6 and v7 UUIDs
+    pub timestamp: Option<UuidTimestamp>,
+    /// The 14-bit clock sequence, for v1 and v6 UUIDs
+    pub clock_sequence: Option<u16>,
+    /// The node ID (usually a MAC address), for v1 and v6 UUIDs
+    pub node_id: Option<[u8; 6]>,
+    /// The standard `8-4-4-4-12` hex form
+    pub hyphenated: String,
+    /// The base64 form, as used by `Display`
+    pub base64: InlineString,
+}
+
+impl Description {
+    pub(crate) fn new(id: UuidB64, base64: InlineString) -> Description {
+        let uuid = id.uuid();
+        let clock_sequence = match uuid.get_version() {
+            Some(Version::Mac) | Some(Version::SortMac) => {
+                uuid.get_timestamp().map(|ts| ts.to_gregorian().1)
+            }
+            _ => None,
+        };
+        Description {
+            id,
+            version: uuid.get_version(),
+            version_num: uuid.get_version_num(),
+            variant: uuid.get_variant(),
+            timestamp: id.timestamp(),
+            clock_sequence,
+            node_id: uuid.get_node_id(),
+            hyphenated: uuid.hyphenated().to_string(),
+            base64,
+        }
+    }
+}
+
+fn version_name(version: Option<Version>) -> &'static str {
+    match version {
+        Some(Version::Nil) => "nil",
+        Some(Version::Mac) => "time and node",
+        Some(Version::Dce) => "DCE security",
+        Some(Version::Md5) => "MD5 name-based",
+        Some(Version::Random) => "random",
+        Some(Version::Sha1) => "SHA-1 name-based",
+        Some(Version::SortMac) => "reordered time and node",
+        Some(Version::SortRand) => "Unix time-ordered",
+        Some(Version::Custom) => "custom",
+        Some(Version::Max) => "max",
+        _ => "unknown",
+    }
+}
+
+fn variant_name(variant: Variant) -> &'static str {
+    match variant {
+        Variant::NCS => "NCS (reserved)",
+        Variant::RFC4122 => "RFC 4122",
+        Variant::Microsoft => "Microsoft (reserved)",
+        _ => "future (reserved)",
+    }
+}
+
+impl Display for Description {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        writeln!(f, "Base64:     {}", self.base64)?;
+        writeln!(f, "Hyphenated: {}", self.hyphenated)?;
+        writeln!(
+            f,
+            "Version:    {} ({})",
+            self.version_num,
+            version_name(self.version)
+        )?;
+        writeln!(f, "Variant:    {}", variant_name(self.variant))?;
+        if let Some(timestamp) = self.timestamp {
+            writeln!(f, "Timestamp:  {}", timestamp)?;
+        }
+        if let Some(clock_sequence) = self.clock_sequence {
+            writeln!(f, "Clock seq:  {}", clock_sequence)?;
+        }
+        if let Some(node) = self.node_id {
+            writeln!(
+                f,
+                "Node:       {:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+                node[0], node[1], node[2], node[3], node[4], node[5]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::{Builder, Timestamp, Uuid};
+
+    use super::*;
+
+    #[test]
+    fn describes_v1() {
+        let id = UuidB64::from(Uuid::new_v1(
+            Timestamp::from_gregorian_time(0x01ee_833b_04af_c000 + 1234, 0x0123),
+            &[0x02, 0x42, 0xac, 0x11, 0x00, 0x02],
+        ));
+        let description = id.describe();
+        assert_eq!(description.version, Some(Version::Mac));
+        assert_eq!(description.clock_sequence, Some(0x0123));
+        assert_eq!(
+            description.to_string(),
+            format!(
+                "\
+Base64:     {}
+Hyphenated: {}
+Version:    1 (time and node)
+Variant:    RFC 4122
+Timestamp:  2023-11-14T22:13:20.0001234Z
+Clock seq:  291
+Node:       02:42:ac:11:00:02
+",
+                id,
+                id.uuid().hyphenated()
+            )
+        );
+    }
+
+    #[test]
+    fn describes_v7() {
+        let id = UuidB64::from(
+            Builder::from_unix_timestamp_millis(1_700_000_000_000, &[0; 10]).into_uuid(),
+        );
+        let description = id.describe();
+        assert_eq!(description.version_num, 7);
+        assert_eq!(description.clock_sequence, None);
+        assert_eq!(description.node_id, None);
+        assert!(description
+            .to_string()
+            .contains("Timestamp:  2023-11-14T22:13:20Z\n"));
+    }
+}
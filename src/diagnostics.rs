@@ -0,0 +1,213 @@
+//! Explaining parse errors to people
+//!
+//! A [`ParseError`] says what was wrong. [`ParseError::diagnostic`] also
+//! shows where, by printing the input with a caret under the problem, and
+//! recognizes the mistakes people make most often when pasting IDs around.
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::any::Format;
+use crate::errors::ParseError;
+use crate::UuidB64;
+
+/// A likely fix for input that failed to parse, from [`Diagnostic::suggestion`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Suggestion {
+    /// The input is a valid ID in another format, and this is it
+    Reformat { id: UuidB64, from: Format },
+    /// The input is one character longer than an ID
+    OneTooLong,
+    /// The input is one character shorter than an ID
+    OneTooShort,
+}
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Suggestion::Reformat { id, from } => {
+                match from {
+                    Format::Standard | Format::StandardNoPad => f.write_str(
+                        "this is standard base64, which uses `+` and `/` where this ID uses `-` and `_`",
+                    )?,
+                    Format::UrlSafe => f.write_str("this ID does not use `==` padding")?,
+                    _ => write!(f, "this is a UUID in {}", from)?,
+                }
+                write!(f, ", did you mean `{}`?", id)
+            }
+            Suggestion::OneTooLong => f.write_str(
+                "the ID is one character too long, check for a doubled or extra character",
+            ),
+            Suggestion::OneTooShort => {
+                f.write_str("the ID is one character too short, check for a missing character")
+            }
+        }
+    }
+}
+
+/// A [`ParseError`] with the input it came from, created by
+/// [`ParseError::diagnostic`]
+///
+/// Displays as the error, the input with a caret under the problem where
+/// there is one, and a suggested fix where one is known.
+#[derive(Copy, Clone, Debug)]
+pub struct Diagnostic<'a> {
+    error: &'a ParseError,
+    input: &'a str,
+}
+
+impl ParseError {
+    /// Explain this error in terms of the `input` that caused it
+    ///
+    /// Suggestions assume the input was meant to be the default 22 character
+    /// URL-safe form.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let input = "sMHuhm9GTxuNi3hJ51287g==";
+    /// let err = input.parse::<UuidB64>().unwrap_err();
+    /// assert_eq!(
+    ///     err.diagnostic(input).to_string(),
+    ///     "expected 22 characters, found 24\n\
+    ///      \x20   sMHuhm9GTxuNi3hJ51287g==\n\
+    ///      \x20                         ^\n\
+    ///      help: this ID does not use `==` padding, did you mean `sMHuhm9GTxuNi3hJ51287g`?"
+    /// );
+    /// ```
+    pub fn diagnostic<'a>(&'a self, input: &'a str) -> Diagnostic<'a> {
+        Diagnostic { error: self, input }
+    }
+}
+
+impl Diagnostic<'_> {
+    /// The byte offset in the input the caret points at
+    ///
+    /// This can be the length of the input, when something is missing from
+    /// the end.
+    pub fn offset(&self) -> Option<usize> {
+        let offset = match *self.error {
+            ParseError::InvalidCharacter { offset, .. }
+            | ParseError::NonCanonicalTrailingBits { offset, .. } => offset,
+            ParseError::WrongLength { expected, .. } => expected.min(self.input.len()),
+            ParseError::InvalidPadding { .. } => self.input.find('=')?,
+            _ => return None,
+        };
+        Some(offset).filter(|&offset| self.input.is_char_boundary(offset))
+    }
+
+    /// A likely fix, if the input looks like a common mistake
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match UuidB64::parse_any(self.input) {
+            Ok((_, Format::UrlSafeNoPad)) => None,
+            Ok((id, from)) => Some(Suggestion::Reformat { id, from }),
+            Err(_) => match self.input.chars().count() {
+                21 => Some(Suggestion::OneTooShort),
+                23 => Some(Suggestion::OneTooLong),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.error)?;
+        if let Some(offset) = self.offset() {
+            f.write_str("\n    ")?;
+            for c in self.input.chars() {
+                write!(f, "{}", if c.is_control() { ' ' } else { c })?;
+            }
+            write!(
+                f,
+                "\n    {:>1$}",
+                "^",
+                self.input[..offset].chars().count() + 1
+            )?;
+        }
+        if let Some(suggestion) = self.suggestion() {
+            write!(f, "\nhelp: {}", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnose(input: &str) -> String {
+        input
+            .parse::<UuidB64>()
+            .unwrap_err()
+            .diagnostic(input)
+            .to_string()
+    }
+
+    #[test]
+    fn points_at_the_problem() {
+        assert_eq!(
+            diagnose("sMHuhm9GTx!Ni3hJ51287g"),
+            "invalid character '!' at byte offset 10\n    \
+             sMHuhm9GTx!Ni3hJ51287g\n              \
+             ^"
+        );
+        assert_eq!(
+            diagnose("sMHuhm9GTxuNi3hJ51287h"),
+            "the symbol at byte offset 21 sets bits that are not part of the ID\n    \
+             sMHuhm9GTxuNi3hJ51287h\n                         \
+             ^"
+        );
+        assert!(diagnose("sMHuhm 9GTxuN i3h!51 287g")
+            .ends_with("sMHuhm 9GTxuN i3h!51 287g\n                     ^"));
+        // the caret counts characters, not bytes
+        assert!(diagnose("sMHuhm9GTé").ends_with("sMHuhm9GTé\n              ^"));
+    }
+
+    #[test]
+    fn suggests_standard_alphabet() {
+        let err = diagnose("sMH+hm9GTx/Ni3hJ51287g");
+        assert!(err.starts_with("invalid character '+' at byte offset 3"));
+        assert!(err.ends_with(
+            "help: this is standard base64, which uses `+` and `/` where this ID uses `-` \
+             and `_`, did you mean `sMH-hm9GTx_Ni3hJ51287g`?"
+        ));
+    }
+
+    #[test]
+    fn suggests_base64_for_hex() {
+        for input in [
+            "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee",
+            "{b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee}",
+            "urn:uuid:b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee",
+        ] {
+            let err = diagnose(input);
+            assert!(
+                err.ends_with("did you mean `sMHuhm9GTxuNi3hJ51287g`?"),
+                "{}",
+                err
+            );
+        }
+        assert!(diagnose("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee")
+            .contains("this is a UUID in hyphenated hex"));
+    }
+
+    #[test]
+    fn suggests_length_fixes() {
+        let err = diagnose("sMHuhm9GTxuNi3hJ51287");
+        assert!(err.starts_with("expected 22 characters, found 21\n"));
+        assert!(
+            err.ends_with("help: the ID is one character too short, check for a missing character")
+        );
+
+        let err = diagnose("sMHuhm9GTxuNi3hJ51287gg");
+        assert!(err.ends_with(
+            "help: the ID is one character too long, check for a doubled or extra character"
+        ));
+
+        // nothing useful to say about other lengths
+        assert_eq!(
+            diagnose("sMHuhm9G"),
+            "expected 22 characters, found 8\n    sMHuhm9G\n            ^"
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Uuid as SqlUuid;
+use uuid::Uuid;
+
+use crate::encoding::Encoding;
+use crate::UuidB64;
+
+impl<E: Encoding> FromSql<SqlUuid, Pg> for UuidB64<E> {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        let id = <Uuid as FromSql<SqlUuid, Pg>>::from_sql(value)?;
+        Ok(UuidB64::from_uuid(id))
+    }
+}
+
+impl<E: Encoding> ToSql<SqlUuid, Pg> for UuidB64<E> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.0.as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
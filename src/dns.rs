@@ -0,0 +1,157 @@
+//! IDs as DNS labels, for naming Kubernetes objects and subdomains
+//!
+//! Labels are `<prefix>-<id>`, where the ID is 26 characters of lowercase
+//! Crockford base32. Kubernetes resource names follow RFC 1123 (lowercase
+//! letters, digits and `-`, at most 63 characters, starting and ending with a
+//! letter or digit), and some, like Service names, follow the stricter
+//! RFC 1035 rule that labels start with a letter. Labels made here satisfy
+//! both, which is why a prefix is required: the ID itself starts with a
+//! digit.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+
+use crate::errors::ParseError;
+use crate::UuidB64;
+
+/// The maximum length of a DNS label
+pub const MAX_DNS_LABEL_LEN: usize = 63;
+
+const ID_LEN: usize = 26;
+
+fn invalid(reason: &'static str) -> ParseError {
+    ParseError::InvalidDnsLabel(reason)
+}
+
+/// Check the rules that apply to the whole label
+fn check_label(label: &str) -> Result<(), ParseError> {
+    let first = label.bytes().next();
+    if first.is_none() {
+        return Err(invalid("labels must not be empty (RFC 1123)"));
+    }
+    if !first.unwrap().is_ascii_lowercase() {
+        return Err(invalid(
+            "labels must start with a lowercase letter (RFC 1035)",
+        ));
+    }
+    if let Some(c) = label
+        .bytes()
+        .find(|&c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-'))
+    {
+        return Err(if c.is_ascii_uppercase() {
+            invalid("labels must be lowercase (RFC 1123)")
+        } else {
+            invalid("labels may only contain lowercase letters, digits and '-' (RFC 1123)")
+        });
+    }
+    if label.ends_with('-') {
+        return Err(invalid("labels must end with a letter or digit (RFC 1123)"));
+    }
+    if label.len() > MAX_DNS_LABEL_LEN {
+        return Err(invalid("labels must be at most 63 characters (RFC 1123)"));
+    }
+    Ok(())
+}
+
+impl<E> UuidB64<E> {
+    /// Render this ID as a DNS label, `<prefix>-<id>`
+    ///
+    /// The prefix must start with a lowercase letter, may only contain
+    /// lowercase letters, digits and `-`, and can be at most 36 characters so
+    /// that the whole label fits in 63. The error says which rule was broken.
+    ///
+    /// Needs the `alloc` feature.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let label = id.to_dns_label("worker").unwrap();
+    /// assert_eq!(label, "worker-5gr7q8cvt69wdrv2vr97knvf7e");
+    /// assert_eq!(UuidB64::parse_dns_label(&label).unwrap(), ("worker", id));
+    ///
+    /// let err = id.to_dns_label("2fast").unwrap_err();
+    /// assert!(err.to_string().contains("must start with a lowercase letter"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_dns_label(&self, prefix: &str) -> Result<String, ParseError> {
+        let mut label = String::with_capacity(prefix.len() + 1 + ID_LEN);
+        label.push_str(prefix);
+        label.push('-');
+        write!(label, "{}", self.crockford()).unwrap();
+        label[prefix.len() + 1..].make_ascii_lowercase();
+        check_label(&label)?;
+        Ok(label)
+    }
+}
+
+impl UuidB64 {
+    /// Parse a label written by [`UuidB64::to_dns_label`] into its prefix and ID
+    pub fn parse_dns_label(label: &str) -> Result<(&str, UuidB64), ParseError> {
+        check_label(label)?;
+        let split = label
+            .len()
+            .checked_sub(ID_LEN + 1)
+            .filter(|&split| split > 0 && label.as_bytes()[split] == b'-')
+            .ok_or_else(|| invalid("expected a prefix, '-' and a 26 character ID"))?;
+        let id = UuidB64::parse_crockford(&label[split + 1..])
+            .map_err(|err| err.offset_by(split + 1))?;
+        Ok((&label[..split], id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn reason(result: Result<String, ParseError>) -> &'static str {
+        match result {
+            Err(ParseError::InvalidDnsLabel(reason)) => reason,
+            other => panic!("expected an invalid label, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn roundtrips() {
+        for id in [
+            "sMHuhm9GTxuNi3hJ51287g".parse().unwrap(),
+            UuidB64::from(Uuid::max()),
+            "AYvP5WgAcSOEVniavN7wEg".parse().unwrap(),
+        ] {
+            let label = id.to_dns_label("a").unwrap();
+            assert_eq!(UuidB64::parse_dns_label(&label).unwrap(), ("a", id));
+
+            let prefix = "abcdefghij-abcdefghij-abcdefghij-123";
+            let label = id.to_dns_label(prefix).unwrap();
+            assert_eq!(label.len(), MAX_DNS_LABEL_LEN);
+            assert_eq!(UuidB64::parse_dns_label(&label).unwrap(), (prefix, id));
+        }
+    }
+
+    #[test]
+    fn explains_broken_rules() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert!(reason(id.to_dns_label("")).contains("start with a lowercase letter"));
+        assert!(reason(id.to_dns_label("-pod")).contains("start with a lowercase letter"));
+        assert!(reason(id.to_dns_label("Pod")).contains("start with a lowercase letter"));
+        assert!(reason(id.to_dns_label("poD")).contains("must be lowercase"));
+        assert!(reason(id.to_dns_label("my_pod")).contains("may only contain"));
+        assert!(
+            reason(id.to_dns_label("abcdefghij-abcdefghij-abcdefghij-1234"))
+                .contains("at most 63 characters")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_labels() {
+        assert!(UuidB64::parse_dns_label("").is_err());
+        assert!(UuidB64::parse_dns_label("worker").is_err());
+        assert!(UuidB64::parse_dns_label("worker-").is_err());
+        assert!(UuidB64::parse_dns_label("worker5gr7q8cvt69wdrv2vr97knvf7e").is_err());
+        assert!(UuidB64::parse_dns_label("w-9gr7q8cvt69wdrv2vr97knvf7e").is_err());
+        assert!(UuidB64::parse_dns_label("w-5GR7Q8CVT69WDRV2VR97KNVF7E").is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! The base64 alphabets and padding rules a `UuidB64` can use
+//!
+//! `UuidB64` is generic over an [`Encoding`], which picks the base64 engine
+//! used by `Display`, `FromStr`, `to_istring`, `to_buf` and, when they are
+//! enabled, serde. The default is [`UrlSafeNoPad`], so a plain `UuidB64`
+//! behaves exactly as it always has.
+//!
+//! ```rust
+//! # use uuid_b64::UuidB64;
+//! # use uuid_b64::encoding::Standard;
+//! let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+//! let standard = id.with_encoding::<Standard>();
+//! assert_eq!(standard.to_string(), "sMHuhm9GTxuNi3hJ51287g==");
+//!
+//! let parsed: UuidB64<Standard> = "sMHuhm9GTxuNi3hJ51287g==".parse().unwrap();
+//! assert_eq!(parsed, standard);
+//! ```
+//!
+//! To use your own alphabet implement `Encoding` on a marker type:
+//!
+//! ```rust
+//! # use uuid_b64::UuidB64;
+//! use base64::alphabet::Alphabet;
+//! use base64::engine::general_purpose::{GeneralPurpose, NO_PAD};
+//! use uuid_b64::encoding::Encoding;
+//!
+//! const DOTTED: GeneralPurpose = GeneralPurpose::new(
+//!     &match Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789._") {
+//!         Ok(alphabet) => alphabet,
+//!         Err(_) => panic!("invalid alphabet"),
+//!     },
+//!     NO_PAD,
+//! );
+//!
+//! #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+//! struct Dotted;
+//!
+//! impl Encoding for Dotted {
+//!     const ENGINE: &'static GeneralPurpose = &DOTTED;
+//! }
+//!
+//! let id: UuidB64 = "-_-_-_-_-_-_-_-_-_-_-w".parse().unwrap();
+//! assert_eq!(id.with_encoding::<Dotted>().to_string(), "._._._._._._._._._._.w");
+//! ```
+
+use core::fmt::Debug;
+use core::hash::Hash;
+
+use base64::alphabet::Alphabet;
+use base64::engine::general_purpose::{self, GeneralPurpose};
+use base64::Engine;
+use uuid::Uuid;
+
+/// A base64 alphabet and padding configuration for `UuidB64`
+///
+/// The supertraits exist so that `UuidB64<E>` can derive the usual traits;
+/// implementors are expected to be empty marker types.
+pub trait Encoding: Copy + Debug + Default + Eq + Ord + Hash + Send + Sync + 'static {
+    /// The engine used to encode and decode
+    const ENGINE: &'static GeneralPurpose;
+}
+
+/// The number of base64 symbols in a UUID, not counting padding
+pub(crate) const UUID_SYMBOLS: usize = 22;
+
+/// The symbol `E` uses for the 6-bit `value`
+pub(crate) fn symbol_for<E: Encoding>(value: u8) -> u8 {
+    let mut encoded = [0; 4];
+    E::ENGINE
+        .encode_slice([value << 2], &mut encoded)
+        .expect("one byte encodes to at most 4 symbols");
+    encoded[0]
+}
+
+/// Rebuild a UUID from the values of its 22 symbols
+///
+/// 22 symbols hold 132 bits, so this returns `None` if any of the last
+/// symbol's four unused bits are set: no encoder writes that string.
+pub(crate) const fn uuid_from_symbols(values: &[u8; UUID_SYMBOLS]) -> Option<Uuid> {
+    let last = values[UUID_SYMBOLS - 1];
+    if last & 0x0f != 0 {
+        return None;
+    }
+    // a loop rather than a fold so that `UuidB64::parse_const` can use this
+    let mut high = 0u128;
+    let mut i = 0;
+    while i < UUID_SYMBOLS - 1 {
+        high = high << 6 | values[i] as u128;
+        i += 1;
+    }
+    Some(Uuid::from_u128(high << 2 | (last >> 4) as u128))
+}
+
+/// The URL-safe alphabet (`-` and `_`) with no padding, 22 characters
+///
+/// This is the default encoding.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UrlSafeNoPad;
+
+impl Encoding for UrlSafeNoPad {
+    const ENGINE: &'static GeneralPurpose = &general_purpose::URL_SAFE_NO_PAD;
+}
+
+/// The URL-safe alphabet (`-` and `_`) with `==` padding, 24 characters
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UrlSafe;
+
+impl Encoding for UrlSafe {
+    const ENGINE: &'static GeneralPurpose = &general_purpose::URL_SAFE;
+}
+
+/// The standard alphabet (`+` and `/`) with `==` padding, 24 characters
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Standard;
+
+impl Encoding for Standard {
+    const ENGINE: &'static GeneralPurpose = &general_purpose::STANDARD;
+}
+
+/// The standard alphabet (`+` and `/`) with no padding, 22 characters
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StandardNoPad;
+
+impl Encoding for StandardNoPad {
+    const ENGINE: &'static GeneralPurpose = &general_purpose::STANDARD_NO_PAD;
+}
+
+/// The [`Sortable`] alphabet: the URL-safe characters, in ASCII order
+const SORTABLE_ALPHABET: Alphabet =
+    match Alphabet::new("-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz") {
+        Ok(alphabet) => alphabet,
+        Err(_) => panic!("the sortable alphabet is valid"),
+    };
+
+const SORTABLE: GeneralPurpose = GeneralPurpose::new(&SORTABLE_ALPHABET, general_purpose::NO_PAD);
+
+/// An order-preserving alphabet with no padding, 22 characters
+///
+/// The URL-safe alphabet is not in ASCII order (`A-Z a-z 0-9 - _`), so
+/// sorting encoded IDs as strings gives a different order than sorting the
+/// UUIDs. This alphabet uses the same 64 characters but in ASCII order
+/// (`- 0-9 A-Z _ a-z`), so string order and `Ord` always agree. That means
+/// v7 IDs stored in text columns, used as object storage keys or grepped out
+/// of logs sort by creation time.
+///
+/// These strings are not interchangeable with the default encoding: the
+/// same UUID encodes to a different string.
+///
+/// ```rust
+/// # use uuid_b64::UuidB64;
+/// # use uuid_b64::encoding::Sortable;
+/// # #[cfg(all(feature = "std", feature = "rng"))]
+/// # fn main() {
+/// let first = UuidB64::new_v7().with_encoding::<Sortable>();
+/// let second = UuidB64::new_v7().with_encoding::<Sortable>();
+/// assert!(first < second);
+/// assert!(first.to_string() < second.to_string());
+/// # }
+/// # #[cfg(not(all(feature = "std", feature = "rng")))]
+/// # fn main() {}
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sortable;
+
+impl Encoding for Sortable {
+    const ENGINE: &'static GeneralPurpose = &SORTABLE;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{B64Bytes, UuidB64};
+
+    use super::*;
+
+    fn roundtrips<E: Encoding>(expected: &str) {
+        let id: UuidB64 = "-_-_-_-_-_-_-_-_-_-_-w".parse().unwrap();
+        let id = id.with_encoding::<E>();
+        assert_eq!(id.to_string(), expected);
+        #[cfg(feature = "std")]
+        assert_eq!(&*id.to_istring(), expected);
+        assert_eq!(B64Bytes::<16, E>::encoded_len(), expected.len());
+        assert_eq!(expected.parse::<UuidB64<E>>().unwrap(), id);
+    }
+
+    #[test]
+    fn builtin_encodings() {
+        roundtrips::<UrlSafeNoPad>("-_-_-_-_-_-_-_-_-_-_-w");
+        roundtrips::<UrlSafe>("-_-_-_-_-_-_-_-_-_-_-w==");
+        roundtrips::<Standard>("+/+/+/+/+/+/+/+/+/+/+w==");
+        roundtrips::<StandardNoPad>("+/+/+/+/+/+/+/+/+/+/+w");
+        roundtrips::<Sortable>("yzyzyzyzyzyzyzyzyzyzyk");
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn sortable_string_order_matches_ord() {
+        use uuid::Uuid;
+
+        let mut ids: Vec<UuidB64<Sortable>> =
+            (0..1000).map(|_| UuidB64::new().with_encoding()).collect();
+        // the extremes of every 6-bit group
+        ids.push(UuidB64::from_uuid(Uuid::nil()));
+        ids.push(UuidB64::from_uuid(Uuid::max()));
+        ids.push(UuidB64::from_uuid(Uuid::from_u128(1)));
+        ids.push(UuidB64::from_uuid(Uuid::from_u128(u128::MAX - 1)));
+
+        let mut by_ord = ids.clone();
+        by_ord.sort();
+        let mut by_string = ids;
+        by_string.sort_by_key(|id| id.to_string());
+        assert_eq!(by_ord, by_string);
+
+        // the default alphabet does not have this property
+        let a: UuidB64 = UuidB64::from_uuid(Uuid::from_u128(51 << 122)); // "z..."
+        let b: UuidB64 = UuidB64::from_uuid(Uuid::from_u128(52 << 122)); // "0..."
+        assert!(a < b);
+        assert!(a.to_string() > b.to_string());
+    }
+
+    #[cfg(all(feature = "std", feature = "rng"))]
+    #[test]
+    fn sortable_v7_strings_sort_chronologically() {
+        let ids: Vec<String> = (0..1000)
+            .map(|_| UuidB64::new_v7().with_encoding::<Sortable>().to_string())
+            .collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn rejects_other_alphabets() {
+        assert!("+/+/+/+/+/+/+/+/+/+/+w".parse::<UuidB64>().is_err());
+        assert!("-_-_-_-_-_-_-_-_-_-_-w"
+            .parse::<UuidB64<StandardNoPad>>()
+            .is_err());
+    }
+}
@@ -1,10 +1,252 @@
-use error_chain::error_chain;
+//! The errors returned when an ID can not be parsed or converted
 
-error_chain! {
-    errors {
-        ParseError(t: String) {
-            description("Unable to parse UUID")
-            display("Invalid Base64 representation for UUID: '{}'", t)
+use core::fmt::{Display, Formatter, Result as FmtResult};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use base64::{DecodeError, DecodeSliceError};
+
+/// Why a string could not be parsed into an ID
+///
+/// No variant holds a copy of the input, so rejecting bad input never
+/// allocates. Byte offsets are into the string that was parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The input does not have the number of characters the format needs
+    WrongLength { expected: usize, found: usize },
+    /// The character starting at byte `offset` is not part of the format
+    InvalidCharacter {
+        offset: usize,
+        found: char,
+        source: Option<DecodeError>,
+    },
+    /// The `=` padding is missing, or present where the encoding has none
+    InvalidPadding { source: Option<DecodeError> },
+    /// The last symbol, at byte `offset`, sets bits that are not part of the
+    /// ID, so no encoder would have written it
+    NonCanonicalTrailingBits {
+        offset: usize,
+        source: Option<DecodeError>,
+    },
+    /// The input is well formed but holds a value larger than 128 bits
+    OutOfRange,
+    /// The check symbol does not match the rest of the ID
+    CheckSymbolMismatch,
+    /// The input has the wrong number of words
+    WrongWordCount { expected: usize, found: usize },
+    /// Word number `index` (from 0) of a mnemonic is not in the word list
+    UnknownWord {
+        index: usize,
+        suggestion: &'static str,
+    },
+    /// The input is not a valid DNS label, for the given reason
+    InvalidDnsLabel(&'static str),
+    /// A version-checked type was given a UUID of another version
+    WrongVersion { expected: usize, found: usize },
+    /// A version-checked type was given a UUID that is not RFC 4122
+    WrongVariant,
+}
+
+impl ParseError {
+    /// The error for the character starting at byte `offset` of `s`
+    pub(crate) fn invalid_character(s: &str, offset: usize) -> ParseError {
+        ParseError::InvalidCharacter {
+            offset,
+            found: s[offset..].chars().next().unwrap_or('\0'),
+            source: None,
+        }
+    }
+
+    /// Move any byte offset in this error `by` bytes later, for errors found
+    /// in a substring that starts `by` bytes into the input
+    pub(crate) fn offset_by(mut self, by: usize) -> ParseError {
+        match &mut self {
+            ParseError::InvalidCharacter { offset, .. }
+            | ParseError::NonCanonicalTrailingBits { offset, .. } => *offset += by,
+            _ => {}
+        }
+        self
+    }
+
+    /// Translate an error from the base64 engine, keeping it as the source
+    ///
+    /// `offsets` maps the index of each symbol that was decoded to its byte
+    /// offset in `s`, which differs when separators were removed first.
+    pub(crate) fn from_base64(
+        s: &str,
+        expected: usize,
+        offsets: &[usize],
+        err: DecodeSliceError,
+    ) -> ParseError {
+        let source = match err {
+            DecodeSliceError::DecodeError(source) => source,
+            DecodeSliceError::OutputSliceTooSmall => {
+                return ParseError::WrongLength {
+                    expected,
+                    found: s.len(),
+                }
+            }
+        };
+        match source {
+            DecodeError::InvalidByte(index, _) => ParseError::InvalidCharacter {
+                offset: offsets[index],
+                found: s[offsets[index]..].chars().next().unwrap_or('\0'),
+                source: Some(source),
+            },
+            DecodeError::InvalidLastSymbol(index, _) => ParseError::NonCanonicalTrailingBits {
+                offset: offsets[index],
+                source: Some(source),
+            },
+            DecodeError::InvalidPadding => ParseError::InvalidPadding {
+                source: Some(source),
+            },
+            DecodeError::InvalidLength(_) => ParseError::WrongLength {
+                expected,
+                found: s.len(),
+            },
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ParseError::WrongLength { expected, found } => {
+                write!(f, "expected {} characters, found {}", expected, found)
+            }
+            ParseError::InvalidCharacter { offset, found, .. } => {
+                write!(f, "invalid character {:?} at byte offset {}", found, offset)
+            }
+            ParseError::InvalidPadding { .. } => write!(f, "invalid padding"),
+            ParseError::NonCanonicalTrailingBits { offset, .. } => write!(
+                f,
+                "the symbol at byte offset {} sets bits that are not part of the ID",
+                offset
+            ),
+            ParseError::OutOfRange => write!(f, "the value does not fit in 128 bits"),
+            ParseError::CheckSymbolMismatch => write!(
+                f,
+                "check symbol does not match the rest of the ID, it probably has a typo"
+            ),
+            ParseError::WrongWordCount { expected, found } => {
+                write!(f, "expected {} words, found {}", expected, found)
+            }
+            ParseError::UnknownWord { index, suggestion } => write!(
+                f,
+                "word {} is not in the word list, did you mean '{}'?",
+                index + 1,
+                suggestion
+            ),
+            ParseError::InvalidDnsLabel(reason) => write!(f, "invalid DNS label: {}", reason),
+            ParseError::WrongVersion { expected, found } => write!(
+                f,
+                "expected a version {} UUID, found version {}",
+                expected, found
+            ),
+            ParseError::WrongVariant => write!(f, "expected an RFC 4122 variant UUID"),
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidCharacter { source, .. }
+            | ParseError::InvalidPadding { source }
+            | ParseError::NonCanonicalTrailingBits { source, .. } => {
+                source.as_ref().map(|e| e as &(dyn Error + 'static))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A UUID timestamp that the target date-time type can not represent
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimestampOutOfRange;
+
+impl Display for TimestampOutOfRange {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "UUID timestamp can not be represented by the target type"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for TimestampOutOfRange {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UuidB64;
+
+    #[test]
+    fn distinct_kinds() {
+        assert_eq!(
+            "sMHuhm9G".parse::<UuidB64>().unwrap_err(),
+            ParseError::WrongLength {
+                expected: 22,
+                found: 8
+            }
+        );
+        assert!(matches!(
+            "sMHuhm9GTx!Ni3hJ51287g".parse::<UuidB64>().unwrap_err(),
+            ParseError::InvalidCharacter {
+                offset: 10,
+                found: '!',
+                ..
+            }
+        ));
+        assert!(matches!(
+            "sMHuhm9GTxuNi3hJ51287h".parse::<UuidB64>().unwrap_err(),
+            ParseError::NonCanonicalTrailingBits { offset: 21, .. }
+        ));
+    }
+
+    #[test]
+    fn grouped_offsets_point_into_the_input() {
+        let invalid = |s: &str| match s.parse::<UuidB64>().unwrap_err() {
+            ParseError::InvalidCharacter { offset, found, .. } => (offset, found),
+            err => panic!("{:?} for {}", err, s),
+        };
+        assert_eq!(invalid("sMHuhm-9GTxuN-i3h!51-287g"), (17, '!'));
+        assert_eq!(invalid("sMHuhm-9GTxuN-i3hé51-287g"), (17, 'é'));
+        assert_eq!(
+            invalid("ChCwQT\u{2009}Si!hOL\u{2009}lihW65\u{2009}SJOw"),
+            (11, '!')
+        );
+        assert!(matches!(
+            "sMHuhm-9GTxuN-i3hJ51-287h".parse::<UuidB64>().unwrap_err(),
+            ParseError::NonCanonicalTrailingBits { offset: 24, .. }
+        ));
+        assert!("ChCwQT\u{2009}Si!hOL\u{2009}lihW65\u{2009}SJOw"
+            .parse::<crate::UuidB64V4>()
+            .is_err());
+        assert!(matches!(
+            "9GTxuN\u{2009}0U/*é"
+                .parse::<crate::B64Bytes<8>>()
+                .unwrap_err(),
+            ParseError::InvalidCharacter {
+                offset: 11,
+                found: '/',
+                ..
+            }
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source_is_the_base64_error() {
+        let err = "sMHuhm9GTx!Ni3hJ51287g".parse::<UuidB64>().unwrap_err();
+        let source = err.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<DecodeError>(),
+            Some(&DecodeError::InvalidByte(10, b'!'))
+        );
+        assert_eq!(err.to_string(), "invalid character '!' at byte offset 10");
+    }
+}
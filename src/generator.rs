@@ -0,0 +1,103 @@
+use std::sync::Mutex;
+
+use uuid::{ContextV7, Timestamp, Uuid};
+
+use crate::UuidB64;
+
+/// The generator backing [`UuidB64::new_v7`]
+pub(crate) static GLOBAL_V7: V7Generator = V7Generator::new();
+
+/// A source of strictly increasing v7 UUIDs
+///
+/// Every ID handed out by a single generator sorts after every ID it handed
+/// out before, even when several are created within the same millisecond or
+/// from several threads at once. Within a millisecond the ordering comes from
+/// a counter that is reseeded with random bits each time the clock ticks
+/// over, and if the system clock steps backwards the generator keeps using
+/// its last timestamp until the clock catches up.
+///
+/// Most code should just call [`UuidB64::new_v7`], which uses a single
+/// process-wide generator. Create your own if you want a sequence that is
+/// independent of everything else in the process:
+///
+/// ```rust
+/// # use uuid_b64::V7Generator;
+/// static ORDER_IDS: V7Generator = V7Generator::new();
+///
+/// let first = ORDER_IDS.generate();
+/// let second = ORDER_IDS.generate();
+/// assert!(first < second);
+/// ```
+#[derive(Debug)]
+pub struct V7Generator {
+    context: Mutex<ContextV7>,
+}
+
+impl V7Generator {
+    /// Create a new generator, usable in a `static`
+    pub const fn new() -> V7Generator {
+        V7Generator {
+            context: Mutex::new(ContextV7::new()),
+        }
+    }
+
+    /// Generate the next v7 UUID in this generator's sequence
+    pub fn generate(&self) -> UuidB64 {
+        UuidB64::from_uuid(Uuid::new_v7(Timestamp::now(&self.context)))
+    }
+}
+
+impl Default for V7Generator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use uuid::Version;
+
+    use super::*;
+
+    #[test]
+    fn generates_v7() {
+        let id = V7Generator::new().generate();
+        assert_eq!(id.uuid().get_version(), Some(Version::SortRand));
+    }
+
+    #[test]
+    fn strictly_increasing_within_a_thread() {
+        let gen = V7Generator::new();
+        let ids: Vec<UuidB64> = (0..10_000).map(|_| gen.generate()).collect();
+        for pair in ids.windows(2) {
+            assert!(pair[0] < pair[1], "{:?} !< {:?}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn strictly_increasing_across_threads() {
+        let gen = Arc::new(V7Generator::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let gen = Arc::clone(&gen);
+                thread::spawn(move || (0..2_000).map(|_| gen.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut all = Vec::new();
+        for handle in handles {
+            let ids = handle.join().unwrap();
+            for pair in ids.windows(2) {
+                assert!(pair[0] < pair[1]);
+            }
+            all.extend(ids);
+        }
+        let count = all.len();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), count);
+    }
+}
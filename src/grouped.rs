@@ -0,0 +1,145 @@
+//! Splitting the base64 form into groups that are easier to compare by eye
+
+use core::fmt::{Display, Formatter, Result as FmtResult, Write};
+
+use crate::encoding::{Encoding, UrlSafeNoPad};
+use crate::UuidB64;
+
+/// The number of symbols in every group but the last
+pub const GROUP_LEN: usize = 6;
+
+/// Displays a `UuidB64` in groups of [`GROUP_LEN`] symbols, created by
+/// [`UuidB64::grouped`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Grouped<E: Encoding = UrlSafeNoPad> {
+    id: UuidB64<E>,
+    separator: char,
+}
+
+impl<E: Encoding> Grouped<E> {
+    /// Put `separator` between the groups instead of `-`
+    pub fn with_separator(mut self, separator: char) -> Grouped<E> {
+        self.separator = separator;
+        self
+    }
+}
+
+impl<E: Encoding> Display for Grouped<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let encoded = self.id.as_b64_bytes().to_stack_str();
+        for (i, group) in encoded.as_bytes().chunks(GROUP_LEN).enumerate() {
+            if i > 0 {
+                f.write_char(self.separator)?;
+            }
+            f.write_str(core::str::from_utf8(group).unwrap())?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Encoding> UuidB64<E> {
+    /// Display this ID in groups of six symbols, separated by `-` or the
+    /// character passed to [`Grouped::with_separator`]
+    ///
+    /// `FromStr` accepts the grouped form as well as the plain one.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.grouped().to_string(), "sMHuhm-9GTxuN-i3hJ51-287g");
+    /// assert_eq!(id.grouped().with_separator(' ').to_string(), "sMHuhm 9GTxuN i3hJ51 287g");
+    ///
+    /// let parsed: UuidB64 = "sMHuhm 9GTxuN i3hJ51 287g".parse().unwrap();
+    /// assert_eq!(parsed, id);
+    /// ```
+    pub fn grouped(&self) -> Grouped<E> {
+        Grouped {
+            id: *self,
+            separator: '-',
+        }
+    }
+}
+
+/// Remove the separators from a grouped ID, writing the symbols into `buf`
+/// and the byte offset in `s` that each one came from into `offsets`
+///
+/// Returns `None` unless `s` is `len` symbols split into groups of
+/// [`GROUP_LEN`] by the same separator character every time. Letters and
+/// digits are never separators, so a plain string of the wrong length is
+/// not mistaken for a grouped one.
+pub(crate) fn ungroup<'a>(
+    s: &str,
+    len: usize,
+    buf: &'a mut [u8],
+    offsets: &mut [usize],
+) -> Option<&'a [u8]> {
+    let mut written = 0;
+    let mut separator = None;
+    let mut chars = s.char_indices();
+    while written < len {
+        if written > 0 && written % GROUP_LEN == 0 {
+            let (_, found) = chars.next()?;
+            if found.is_ascii_alphanumeric() || *separator.get_or_insert(found) != found {
+                return None;
+            }
+        }
+        let (offset, c) = chars.next()?;
+        *buf.get_mut(written)? = u8::try_from(c).ok()?;
+        *offsets.get_mut(written)? = offset;
+        written += 1;
+    }
+    match chars.next() {
+        None => Some(&buf[..len]),
+        Some(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{Sortable, Standard};
+
+    #[test]
+    fn roundtrips() {
+        for id in ["sMHuhm9GTxuNi3hJ51287g", "AYvP5WgAcSOEVniavN7wEg"] {
+            let id: UuidB64 = id.parse().unwrap();
+            for separator in ['-', ' ', '.', '\u{2009}'] {
+                let grouped = id.grouped().with_separator(separator).to_string();
+                assert_eq!(grouped.parse::<UuidB64>().unwrap(), id, "{}", grouped);
+            }
+
+            let sortable = id.with_encoding::<Sortable>();
+            let grouped = sortable.grouped().to_string();
+            assert_eq!(grouped.parse::<UuidB64<Sortable>>().unwrap(), sortable);
+
+            let standard = id.with_encoding::<Standard>();
+            let grouped = standard.grouped().to_string();
+            assert_eq!(grouped.len(), 27);
+            assert!(grouped.ends_with("=="));
+            assert_eq!(grouped.parse::<UuidB64<Standard>>().unwrap(), standard);
+        }
+    }
+
+    #[test]
+    fn ungroup_checks_layout() {
+        let (mut buf, mut offsets) = ([0; 24], [0; 24]);
+        assert_eq!(
+            ungroup("sMHuhm-9GTxuN-i3hJ51-287g", 22, &mut buf, &mut offsets),
+            Some(&b"sMHuhm9GTxuNi3hJ51287g"[..])
+        );
+        assert_eq!(offsets[..8], [0, 1, 2, 3, 4, 5, 7, 8]);
+        assert_eq!(offsets[21], 24);
+        for bad in [
+            // mixed separators, letters as separators, groups of the wrong
+            // size, and too much input
+            "sMHuhm-9GTxuN i3hJ51-287g",
+            "sMHuhmx9GTxuNxi3hJ51x287g",
+            "sMHuh-m9GTxuN-i3hJ51-287g",
+            "sMHuhm-9GTxuN-i3hJ51-287g-",
+            "sMHuhm-9GTxuN-i3hJ51-287",
+        ] {
+            assert_eq!(ungroup(bad, 22, &mut buf, &mut offsets), None, "{}", bad);
+            assert!(bad.parse::<UuidB64>().is_err(), "{}", bad);
+        }
+    }
+}
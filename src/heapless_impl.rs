@@ -0,0 +1,72 @@
+use heapless::String;
+
+use crate::errors::ParseError;
+use crate::UuidB64;
+
+impl UuidB64 {
+    /// Write the 22 character form into a `heapless::String`, without
+    /// allocating
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let s = id.to_heapless();
+    /// assert_eq!(s, "sMHuhm9GTxuNi3hJ51287g");
+    /// assert_eq!(UuidB64::from_heapless(&s).unwrap(), id);
+    /// ```
+    pub fn to_heapless(&self) -> String<22> {
+        let mut s = String::new();
+        s.push_str(&self.as_b64_bytes().to_stack_str())
+            .expect("the URL-safe form is 22 characters");
+        s
+    }
+
+    /// Parse a `heapless::String` with the same rules as `FromStr`
+    ///
+    /// This is not a `TryFrom` impl, for `String<22>` or `&String<22>`,
+    /// because either would overlap with `UuidB64: From<T>` for every
+    /// `T: Into<Uuid>`.
+    pub fn from_heapless(s: &String<22>) -> Result<UuidB64, ParseError> {
+        s.parse()
+    }
+}
+
+/// Same as [`UuidB64::to_heapless`]
+impl From<UuidB64> for String<22> {
+    fn from(id: UuidB64) -> String<22> {
+        id.to_heapless()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn roundtrips() {
+        for id in [
+            UuidB64::from(Uuid::nil()),
+            UuidB64::from(Uuid::max()),
+            "sMHuhm9GTxuNi3hJ51287g".parse().unwrap(),
+        ] {
+            let s = String::<22>::from(id);
+            assert_eq!(s.as_str(), id.to_string());
+            assert_eq!(UuidB64::from_heapless(&s).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let s = String::<22>::try_from("sMHuhm9GTx!Ni3hJ51287g").unwrap();
+        assert!(matches!(
+            UuidB64::from_heapless(&s),
+            Err(ParseError::InvalidCharacter {
+                offset: 10,
+                found: '!',
+                ..
+            })
+        ));
+    }
+}
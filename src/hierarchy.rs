@@ -0,0 +1,106 @@
+//! Deterministic child IDs derived from a parent ID
+
+use crate::content::sha256_of;
+use crate::v8::{from_payload, payload, payload_from_uuid};
+use crate::UuidB64;
+
+/// The low bits of a child's payload hold its index
+const INDEX_BITS: u32 = 32;
+
+/// The 90 bits of a child's payload that identify its parent and label
+///
+/// These come from the same SHA-256 v8 ID as `UuidB64::from_sha256`, of the
+/// parent's 16 bytes followed by the label. The parent is always 16 bytes, so
+/// no two parent and label pairs hash the same bytes.
+fn lineage<E>(parent: &UuidB64<E>, label: &str) -> u128 {
+    let id = sha256_of(&[parent.uuid().as_bytes(), label.as_bytes()]);
+    payload_from_uuid(id.uuid().as_u128()) >> INDEX_BITS
+}
+
+impl<E> UuidB64<E> {
+    /// Derive a stable child ID from this ID, a label and an index
+    ///
+    /// The same parent, label and index always produce the same child, so
+    /// sub-resources created by a retried job get the same IDs every time.
+    /// Children are v8 UUIDs: 90 bits identify the parent and label, and the
+    /// last 32 bits hold the index, so siblings sort by index.
+    ///
+    /// Derivation is not meant to hide anything: anyone who knows the parent
+    /// and label can compute the children.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let invoice: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let line = invoice.derive("invoice-line", 3);
+    /// assert_eq!(line, invoice.derive("invoice-line", 3));
+    /// assert_ne!(line, invoice.derive("invoice-line", 4));
+    ///
+    /// assert!(line.is_derived_from(&invoice, "invoice-line"));
+    /// assert_eq!(line.derived_index(&invoice, "invoice-line"), Some(3));
+    /// assert!(!line.is_derived_from(&invoice, "payment"));
+    /// ```
+    pub fn derive(&self, label: &str, index: u32) -> UuidB64<E> {
+        from_payload((lineage(self, label) << INDEX_BITS) | u128::from(index)).with_encoding()
+    }
+
+    /// If this ID was derived from `parent` and `label`, the index it was derived with
+    pub fn derived_index<F>(&self, parent: &UuidB64<F>, label: &str) -> Option<u32> {
+        let payload = payload(self)?;
+        if payload >> INDEX_BITS == lineage(parent, label) {
+            Some(payload as u32)
+        } else {
+            None
+        }
+    }
+
+    /// Check whether this ID was derived from `parent` and `label`, with any index
+    pub fn is_derived_from<F>(&self, parent: &UuidB64<F>, label: &str) -> bool {
+        self.derived_index(parent, label).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn children_are_stable_and_distinct() {
+        let parent: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let child = parent.derive("invoice-line", 3);
+        assert_eq!(
+            child.to_string(),
+            parent.derive("invoice-line", 3).to_string()
+        );
+        assert_eq!(child.uuid().get_version_num(), 8);
+
+        assert_ne!(child, parent.derive("invoice-line", 2));
+        assert_ne!(child, parent.derive("invoice-lines", 3));
+        let other: UuidB64 = "AYvP5WgAcSOEVniavN7wEg".parse().unwrap();
+        assert_ne!(child, other.derive("invoice-line", 3));
+        assert!(parent.derive("invoice-line", 2) < child);
+    }
+
+    #[test]
+    fn derived_ids_never_change() {
+        let parent: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(
+            parent.derive("invoice-line", 3).to_string(),
+            "35Kk9VT0hsuDZ4_5AAAAAw"
+        );
+    }
+
+    #[test]
+    fn checks_lineage() {
+        let parent: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let child = parent.derive("line", u32::MAX);
+        let grandchild = child.derive("adjustment", 0);
+
+        assert_eq!(child.derived_index(&parent, "line"), Some(u32::MAX));
+        assert_eq!(grandchild.derived_index(&child, "adjustment"), Some(0));
+        assert!(!grandchild.is_derived_from(&parent, "line"));
+        assert!(!child.is_derived_from(&UuidB64::from(Uuid::nil()), "line"));
+        assert!(!parent.is_derived_from(&parent, "line"));
+    }
+}
@@ -12,6 +12,7 @@
 //! # extern crate uuid_b64;
 //! # use uuid::Uuid;
 //! # use uuid_b64::UuidB64;
+//! # #[cfg(feature = "rng")]
 //! # fn main() {
 //! let known_id = Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap();
 //! let as_b64 = UuidB64::from(known_id);
@@ -25,12 +26,18 @@
 //! let uuidb64 = UuidB64::from(raw_id);
 //! assert_eq!(uuidb64.to_string().len(), 22);
 //! # }
+//! # #[cfg(not(feature = "rng"))]
+//! # fn main() {}
 //! ```
 //!
 //! `UuidB64::new` creates v4 UUIDs, because... that's what I use. I'm open to
 //! hearing arguments about why this is a ridiculous decision and I should have
 //! made `new` be `new_v4`.
 //!
+//! If you want IDs that sort by creation time (for example as B-tree primary
+//! keys) use `UuidB64::new_v7`, which is guaranteed to be strictly increasing
+//! within a process, or a [`V7Generator`] of your own.
+//!
 //! # Why?
 //!
 //! UUIDs are great:
@@ -82,48 +89,290 @@
 //! Just use `UuidB64` everywhere you would use `Uuid`, and use `UuidB64::from`
 //! to create one from an existing UUID.
 //!
+//! Other base64 alphabets and padding rules can be picked with the type
+//! parameter, for example `UuidB64<encoding::Standard>` uses `+` and `/` and
+//! pads to 24 characters, and `UuidB64<encoding::Sortable>` uses an alphabet
+//! in ASCII order so that sorting the strings sorts the UUIDs. See the
+//! [`encoding`] module.
+//!
+//! For IDs that people read out loud or type in, `UuidB64::crockford` gives a
+//! case-insensitive Crockford base32 form with an optional check symbol, and
+//! `UuidB64::base62` and `UuidB64::base58` give forms made only of letters and
+//! digits, which select with a double click. `UuidB64::to_dns_label` gives a
+//! lowercase form that is valid as a Kubernetes resource name or DNS label.
+//! `UuidB64::grouped` splits the usual form into groups of six, like
+//! `sMHuhm-9GTxuN-i3hJ51-287g`, and `FromStr` accepts either form.
+//! `UuidB64::checked` appends a check symbol that catches typos and swapped
+//! characters in the usual base64 form.
+//! `UuidB64::proquint` and `UuidB64::mnemonic` give forms that can be read
+//! out loud, and `UuidB64::base45` gives a form that fits a QR code's compact
+//! alphanumeric mode.
+//!
+//! `FromStr` only accepts the form `Display` writes, or the grouped form.
+//! `UuidB64::parse_with` takes [`ParseOptions`] to also accept padding,
+//! surrounding whitespace or the standard `+` and `/` symbols.
+//! Every parser returns a [`ParseError`] that says what was wrong and at
+//! which byte offset, without allocating. `ParseError::diagnostic` renders
+//! it for people, with a caret under the problem and a suggested fix for
+//! common mistakes like pasting a hyphenated UUID.
+//! IDs that are hard-coded can be checked at compile time with the
+//! [`uuid_b64!`] macro, or parsed in any `const` with `UuidB64::parse_const`.
+//! `UuidB64::parse_any` accepts the hyphenated, simple, braced and URN hex
+//! forms as well as every base64 variant, and reports which one it found,
+//! which helps while clients move over from hyphenated IDs.
+//!
+//! Other fixed-size IDs, like 8 byte Snowflake IDs or 32 byte hashes, can
+//! use the same text form with [`B64Bytes`], which `UuidB64` is built on.
+//!
+//! If you need to embed your own data, like a shard or tenant number, in your
+//! IDs then use a [`V8Builder`] to lay it out in a v8 (custom) UUID.
+//!
+//! If a field must only ever hold one version of UUID, use one of the
+//! version-checked wrappers like `UuidB64V4` or `UuidB64V7` instead. They
+//! display the same way, but parsing, deserializing or loading them from the
+//! database fails if the embedded version or variant is wrong.
+//!
 //! ## Features
 //!
+//! * `std` (on by default) enables `std::error::Error` impls, conversion to
+//!   `SystemTime`, `UuidB64::describe` and `UuidB64::to_istring`. Without it
+//!   the crate is `no_std`, and encoding, parsing and `Display` still work.
+//! * `alloc` enables the methods that produce a `String`, like
+//!   `UuidB64::to_buf` and `UuidB64::to_dns_label`. `std` turns it on.
+//! * `rng` (on by default) enables the constructors that need random numbers:
+//!   `UuidB64::new`, `Default` and `V8Builder::random_fill`. `UuidB64::new_v7`
+//!   and `V7Generator` also read the clock, so they need `std` as well.
 //! * `serde` enables serialization/deserialization via Serde.
+//! * `defmt` enables logging IDs with `defmt`, in their base64 form.
+//! * `heapless` enables `UuidB64::to_heapless` and `UuidB64::from_heapless`,
+//!   which convert to and from a `heapless::String<22>` without an allocator.
 //! * `diesel-uuid` enables integration with Diesel's UUID support, this is
 //!   only tested on postgres, PRs welcome for other DBs.
+//! * `chrono`, `time` and `jiff` enable converting the timestamps embedded in
+//!   v1, v6 and v7 IDs into those crates' types.
+//! * `sha2` and `blake3` enable `UuidB64::from_sha256` and
+//!   `UuidB64::from_blake3`, which create content-addressed v8 IDs. `sha2`
+//!   also enables `UuidB64::derive` for deterministic child IDs.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
-use std::convert::From;
-use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
-use std::str::FromStr;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::convert::From;
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use core::str::FromStr;
 
-use base64::display::Base64Display;
-use base64::engine::general_purpose::URL_SAFE_NO_PAD;
-use base64::Engine;
+#[cfg(feature = "std")]
 use inlinable_string::inline_string::InlineString;
 use uuid::Uuid;
 
+use crate::encoding::{Encoding, UrlSafeNoPad};
+
+pub use crate::alphanumeric::{Base58, Base62};
+pub use crate::any::Format;
+pub use crate::base45::Base45;
+pub use crate::bytes::{B64Bytes, B64Str, MAX_BYTES};
+pub use crate::checked::Checked;
+pub use crate::crockford::Crockford;
+#[cfg(feature = "std")]
+pub use crate::describe::Description;
+pub use crate::diagnostics::{Diagnostic, Suggestion};
+pub use crate::dns::MAX_DNS_LABEL_LEN;
+pub use crate::errors::{ParseError, TimestampOutOfRange};
+#[cfg(all(feature = "std", feature = "rng"))]
+pub use crate::generator::V7Generator;
+pub use crate::grouped::{Grouped, GROUP_LEN};
+pub use crate::options::ParseOptions;
+#[cfg(feature = "serde")]
+pub use crate::serde_impl::any_format;
+pub use crate::speakable::{Mnemonic, Proquint};
+pub use crate::timestamp::{ByTimestamp, UuidTimestamp};
+pub use crate::v8::{V8Builder, V8Field, V8_PAYLOAD_BITS};
+pub use crate::versioned::{
+    UuidB64V1, UuidB64V3, UuidB64V4, UuidB64V5, UuidB64V6, UuidB64V7, UuidB64V8,
+};
+
+mod alphanumeric;
+mod any;
+mod base45;
+mod bytes;
+mod checked;
+#[cfg(any(feature = "sha2", feature = "blake3"))]
+mod content;
+mod crockford;
+#[cfg(feature = "defmt")]
+mod defmt_impl;
+#[cfg(feature = "std")]
+mod describe;
+mod diagnostics;
 #[cfg(feature = "diesel-uuid")]
-#[macro_use]
-extern crate diesel_derive_newtype;
-
-use crate::errors::{ErrorKind, ResultExt};
-
+mod diesel_impl;
+mod dns;
+pub mod encoding;
 mod errors;
+#[cfg(all(feature = "std", feature = "rng"))]
+mod generator;
+mod grouped;
+#[cfg(feature = "heapless")]
+mod heapless_impl;
+#[cfg(feature = "sha2")]
+mod hierarchy;
+mod literal;
+mod options;
 #[cfg(feature = "serde")]
 mod serde_impl;
+mod speakable;
+mod timestamp;
+mod v8;
+mod versioned;
 
 /// It's a Uuid that displays as Base 64
+///
+/// The [`Encoding`] parameter picks the base64 alphabet
+/// and padding, the default is URL-safe with no padding. Constructors are
+/// only defined for the default encoding, use
+/// [`with_encoding`](UuidB64::with_encoding) to switch to another one.
+///
+/// The text form comes from the [`B64Bytes<16>`](B64Bytes) this wraps.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-#[cfg_attr(feature = "diesel-uuid", derive(DieselNewType))]
-pub struct UuidB64(uuid::Uuid);
+#[cfg_attr(
+    feature = "diesel-uuid",
+    derive(diesel::AsExpression, diesel::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel-uuid", diesel(sql_type = diesel::sql_types::Uuid))]
+pub struct UuidB64<E = UrlSafeNoPad>(B64Bytes<16, E>);
 
 impl UuidB64 {
+    /// The standard namespace for fully-qualified domain names
+    pub const NAMESPACE_DNS: UuidB64 = UuidB64::from_uuid(Uuid::NAMESPACE_DNS);
+    /// The standard namespace for URLs
+    pub const NAMESPACE_URL: UuidB64 = UuidB64::from_uuid(Uuid::NAMESPACE_URL);
+    /// The standard namespace for ISO OIDs
+    pub const NAMESPACE_OID: UuidB64 = UuidB64::from_uuid(Uuid::NAMESPACE_OID);
+    /// The standard namespace for X.500 DNs
+    pub const NAMESPACE_X500: UuidB64 = UuidB64::from_uuid(Uuid::NAMESPACE_X500);
+
     /// Generate a new v4 Uuid
+    #[cfg(feature = "rng")]
     pub fn new() -> UuidB64 {
-        UuidB64(Uuid::new_v4())
+        UuidB64::from_uuid(Uuid::new_v4())
+    }
+
+    /// Generate a new v7 (time-ordered) Uuid
+    ///
+    /// Every ID returned by this function sorts after every ID it returned
+    /// before in the same process, including across threads. See
+    /// [`V7Generator`] for the details.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let first = UuidB64::new_v7();
+    /// let second = UuidB64::new_v7();
+    /// assert!(first < second);
+    /// assert!(first.to_string() != second.to_string());
+    /// ```
+    #[cfg(all(feature = "std", feature = "rng"))]
+    pub fn new_v7() -> UuidB64 {
+        generator::GLOBAL_V7.generate()
+    }
+
+    /// Derive a v3 (MD5) Uuid from a namespace and a name
+    ///
+    /// The same namespace and name always produce the same ID. Prefer
+    /// [`new_v5`](UuidB64::new_v5) unless you need to match IDs some other
+    /// system already generated with v3.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::new_v3(&UuidB64::NAMESPACE_DNS, b"rust-lang.org");
+    /// assert_eq!(id.uuid().to_string(), "c6db027c-615c-3b4d-959e-1a917747ca5a");
+    /// ```
+    pub fn new_v3<E>(namespace: &UuidB64<E>, name: &[u8]) -> UuidB64 {
+        UuidB64::from_uuid(Uuid::new_v3(&namespace.uuid(), name))
+    }
+
+    /// Derive a v5 (SHA-1) Uuid from a namespace and a name
+    ///
+    /// The same namespace and name always produce the same ID. The namespace
+    /// can be one of the standard `NAMESPACE_*` constants or any other
+    /// `UuidB64`, which makes it easy to scope names to your own entities:
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::new_v5(&UuidB64::NAMESPACE_DNS, b"rust-lang.org");
+    /// assert_eq!(id.uuid().to_string(), "c66bbb60-d62e-5f17-a399-3a0bd237c503");
+    ///
+    /// let imports: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let customer = UuidB64::new_v5(&imports, b"customer:1234");
+    /// assert_eq!(customer, UuidB64::new_v5(&imports, b"customer:1234"));
+    /// ```
+    pub fn new_v5<E>(namespace: &UuidB64<E>, name: &[u8]) -> UuidB64 {
+        UuidB64::from_uuid(Uuid::new_v5(&namespace.uuid(), name))
+    }
+}
+
+impl<E> UuidB64<E> {
+    /// Wrap a UUID, usable in `const`s
+    ///
+    /// `UuidB64::from` is usually more convenient, but it is only
+    /// implemented for the default encoding.
+    pub const fn from_uuid(uuid: Uuid) -> UuidB64<E> {
+        UuidB64(B64Bytes::from_bytes(uuid.into_bytes()))
+    }
+
+    /// Change how this ID is encoded, keeping the same UUID
+    pub const fn with_encoding<F>(self) -> UuidB64<F> {
+        UuidB64(self.0.with_encoding())
     }
 
     /// Copy the raw UUID out
-    pub fn uuid(&self) -> Uuid {
-        self.0
+    pub const fn uuid(&self) -> Uuid {
+        Uuid::from_bytes(*self.0.as_bytes())
+    }
+
+    /// Wrap the bytes of a UUID
+    pub const fn from_b64_bytes(bytes: B64Bytes<16, E>) -> UuidB64<E> {
+        UuidB64(bytes)
+    }
+
+    /// The bytes of the UUID, which give this ID its text form
+    pub const fn as_b64_bytes(&self) -> &B64Bytes<16, E> {
+        &self.0
     }
 
+    /// The creation time embedded in this ID, for v1, v6 and v7 UUIDs
+    ///
+    /// Returns `None` for every other version. Use [`ByTimestamp`] to sort
+    /// IDs by this value.
+    pub fn timestamp(&self) -> Option<UuidTimestamp> {
+        UuidTimestamp::from_uuid(&self.uuid())
+    }
+
+    /// Build a report of everything that can be read out of this ID
+    ///
+    /// This includes the version and variant, the timestamp, clock sequence
+    /// and node ID where the version has them, and both the hyphenated and
+    /// base64 forms. Print it with `{}` for a multi-line summary:
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "AYvP5WgAcSOEVniavN7wEg".parse().unwrap();
+    /// println!("{}", id.describe());
+    /// assert_eq!(id.describe().version_num, 7);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn describe(&self) -> Description
+    where
+        E: Encoding,
+    {
+        Description::new(self.with_encoding(), self.to_istring())
+    }
+}
+
+impl<E: Encoding> UuidB64<E> {
     /// Convert this to a new [`InlineString`][]
     ///
     /// `InlineString`s are stack-allocated and therefore faster than
@@ -143,15 +392,9 @@ impl UuidB64 {
     /// zero-allocation for that.
     ///
     /// [`InlineString`]: https://docs.rs/inlinable_string/0.1.9/inlinable_string/inline_string/index.html
+    #[cfg(feature = "std")]
     pub fn to_istring(&self) -> InlineString {
-        let mut buf = InlineString::from("0000000000000000000000"); // not actually zeroes
-        unsafe {
-            let raw_buf = buf.as_mut_slice();
-            URL_SAFE_NO_PAD
-                .encode_slice(self.0.as_bytes(), &mut raw_buf[0..22])
-                .unwrap();
-        }
-        buf
+        InlineString::from(&*self.0.to_stack_str())
     }
 
     /// Write the Base64-encoded UUID into the provided buffer
@@ -169,8 +412,9 @@ impl UuidB64 {
     /// assert_eq!(&buf, "sMHuhm9GTxuNi3hJ51287g");
     /// # }
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn to_buf(&self, buffer: &mut String) {
-        URL_SAFE_NO_PAD.encode_string(self.0.as_bytes(), buffer);
+        self.0.to_buf(buffer);
     }
 }
 
@@ -181,23 +425,19 @@ impl UuidB64 {
 /// let parsed_b64: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
 /// assert_eq!(format!("{:?}", parsed_b64), "UuidB64(sMHuhm9GTxuNi3hJ51287g)");
 /// ```
-impl FromStr for UuidB64 {
-    type Err = errors::ErrorKind;
+impl<E: Encoding> FromStr for UuidB64<E> {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut output = [0; 16];
-        URL_SAFE_NO_PAD
-            .decode_slice(s, &mut output)
-            .chain_err(|| ErrorKind::ParseError(s.into()))?;
-        let id = Uuid::from_bytes(output);
-        Ok(UuidB64(id))
+        s.parse().map(UuidB64)
     }
 }
 
 /// Construct a new V4 (random) UUID
-impl Default for UuidB64 {
+#[cfg(feature = "rng")]
+impl<E> Default for UuidB64<E> {
     fn default() -> Self {
-        Self::new()
+        UuidB64::new().with_encoding()
     }
 }
 
@@ -207,11 +447,11 @@ where
     T: Into<Uuid>,
 {
     fn from(item: T) -> Self {
-        UuidB64(item.into())
+        UuidB64::from_uuid(item.into())
     }
 }
 
-impl Debug for UuidB64 {
+impl<E: Encoding> Debug for UuidB64<E> {
     /// Same as the display formatter, but includes `UuidB64()` around it
     ///
     /// ```rust
@@ -230,7 +470,7 @@ impl Debug for UuidB64 {
     }
 }
 
-impl Display for UuidB64 {
+impl<E: Encoding> Display for UuidB64<E> {
     /// Write Base64 encoding of this UUID
     ///
     /// ```rust
@@ -245,8 +485,7 @@ impl Display for UuidB64 {
     /// # }
     /// ```
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        let wrapper = Base64Display::new(self.0.as_bytes(), &URL_SAFE_NO_PAD);
-        write!(f, "{}", wrapper)
+        Display::fmt(&self.0, f)
     }
 }
 
@@ -254,6 +493,7 @@ impl Display for UuidB64 {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "rng")]
     #[test]
     fn display_is_b64() {
         let id = UuidB64::new();
@@ -262,6 +502,7 @@ mod tests {
         assert_eq!(format!("UuidB64({})", fmted), format!("{:?}", id));
     }
 
+    #[cfg(feature = "rng")]
     #[test]
     fn parse_roundtrips() {
         let original = UuidB64::new();
@@ -270,16 +511,64 @@ mod tests {
         assert_eq!(parsed, original);
     }
 
+    #[cfg(all(feature = "std", feature = "rng"))]
+    #[test]
+    fn new_v7_roundtrips() {
+        let original = UuidB64::new_v7();
+        let parsed: UuidB64 = original.to_string().parse().unwrap();
+        assert_eq!(parsed, original);
+        assert_eq!(parsed.uuid().get_version_num(), 7);
+    }
+
+    #[test]
+    fn name_based_ids_are_deterministic() {
+        let ns: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(UuidB64::new_v5(&ns, b"a"), UuidB64::new_v5(&ns, b"a"));
+        assert_ne!(UuidB64::new_v5(&ns, b"a"), UuidB64::new_v5(&ns, b"b"));
+        assert_ne!(UuidB64::new_v3(&ns, b"a"), UuidB64::new_v5(&ns, b"a"));
+        assert_eq!(
+            UuidB64::new_v5(&UuidB64::NAMESPACE_URL, b"https://example.com").uuid(),
+            Uuid::new_v5(&Uuid::NAMESPACE_URL, b"https://example.com")
+        );
+    }
+
+    #[cfg(feature = "rng")]
     #[test]
     fn from_uuid_works() {
         let _ = UuidB64::from(Uuid::new_v4());
     }
 
+    #[cfg(feature = "rng")]
+    #[test]
+    fn from_uuid_adapters_works() {
+        let uuid = Uuid::new_v4();
+        let id = UuidB64::from(uuid);
+        assert_eq!(UuidB64::from(uuid.hyphenated()), id);
+        assert_eq!(UuidB64::from(uuid.simple()), id);
+        assert_eq!(UuidB64::from(uuid.urn()), id);
+        assert_eq!(UuidB64::from(uuid.braced()), id);
+    }
+
+    #[test]
+    fn from_str_is_strict() {
+        for bad in [
+            "sMHuhm9G",
+            "sMHuhm9GTxuNi3hJ51287",
+            "sMHuhm9GTxuNi3hJ51287gA",
+            "sMHuhm9GTxuNi3hJ51287h",
+            "sMHuhm9GTxuNi3hJ51287g==",
+        ] {
+            assert!(bad.parse::<UuidB64>().is_err(), "{}", bad);
+        }
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn to_istring_works() {
         let b64 = UuidB64::from(Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap());
         assert_eq!(b64.to_istring(), "sMHuhm9GTxuNi3hJ51287g");
 
+        #[cfg(feature = "rng")]
         for _ in 0..10 {
             let b64 = UuidB64::new();
             b64.to_istring();
@@ -289,14 +578,13 @@ mod tests {
 
 #[cfg(all(test, feature = "diesel-uuid"))]
 mod diesel_tests {
-    use diesel;
     use diesel::dsl::sql;
     use diesel::pg::PgConnection;
     use diesel::prelude::*;
 
     use std::env;
 
-    use super::UuidB64;
+    use super::{UuidB64, UuidB64V4, UuidB64V7};
 
     #[derive(Debug, Clone, PartialEq, Identifiable, Insertable, Queryable)]
     #[diesel(table_name = my_entities)]
@@ -350,4 +638,30 @@ mod diesel_tests {
             .execute(&mut conn)
             .expect("Couldn't delete existing object");
     }
+
+    #[test]
+    fn versioned_from_sql_checks_version() {
+        use self::my_entities::dsl::*;
+
+        let mut conn = setup();
+
+        let obj = MyEntity {
+            id: UuidB64::new(),
+            val: 2,
+        };
+
+        diesel::insert_into(my_entities)
+            .values(&obj)
+            .execute(&mut conn)
+            .expect("Couldn't insert struct into my_entities");
+
+        let query = my_entities.select(id).filter(id.eq(&obj.id));
+        let found: UuidB64V4 = query.first(&mut conn).unwrap();
+        assert_eq!(UuidB64::from(found), obj.id);
+        assert!(query.first::<UuidB64V7>(&mut conn).is_err());
+
+        diesel::delete(my_entities.filter(id.eq(&obj.id)))
+            .execute(&mut conn)
+            .expect("Couldn't delete existing object");
+    }
 }
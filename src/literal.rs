@@ -0,0 +1,182 @@
+//! Parsing IDs at compile time, for IDs that are hard-coded in constants
+
+use crate::encoding::{uuid_from_symbols, UUID_SYMBOLS};
+use crate::errors::ParseError;
+use crate::UuidB64;
+
+/// The value of `c` in the URL-safe alphabet
+const fn url_safe_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'-' => Some(62),
+        b'_' => Some(63),
+        _ => None,
+    }
+}
+
+/// The character starting at byte `offset`, which must be a char boundary
+const fn char_at(bytes: &[u8], offset: usize) -> char {
+    let first = bytes[offset] as u32;
+    let (len, mut value) = match first {
+        0x00..=0x7f => (1, first),
+        0xc0..=0xdf => (2, first & 0x1f),
+        0xe0..=0xef => (3, first & 0x0f),
+        _ => (4, first & 0x07),
+    };
+    let mut i = 1;
+    while i < len {
+        value = value << 6 | (bytes[offset + i] as u32 & 0x3f);
+        i += 1;
+    }
+    match char::from_u32(value) {
+        Some(c) => c,
+        None => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+impl UuidB64 {
+    /// Parse the 22 character URL-safe form in a `const` context
+    ///
+    /// This accepts exactly what `FromStr` accepts for the default encoding,
+    /// except the grouped form, and returns the same errors without a base64
+    /// `source()`. Use the [`uuid_b64!`](crate::uuid_b64) macro to turn a
+    /// literal into a constant.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// const SYSTEM_TENANT: UuidB64 = match UuidB64::parse_const("sMHuhm9GTxuNi3hJ51287g") {
+    ///     Ok(id) => id,
+    ///     Err(_) => panic!("invalid system tenant ID"),
+    /// };
+    /// assert_eq!(SYSTEM_TENANT, "sMHuhm9GTxuNi3hJ51287g".parse().unwrap());
+    /// ```
+    pub const fn parse_const(s: &str) -> Result<UuidB64, ParseError> {
+        let bytes = s.as_bytes();
+        if bytes.len() != UUID_SYMBOLS {
+            return Err(ParseError::WrongLength {
+                expected: UUID_SYMBOLS,
+                found: bytes.len(),
+            });
+        }
+        let mut values = [0; UUID_SYMBOLS];
+        let mut i = 0;
+        while i < UUID_SYMBOLS {
+            values[i] = match url_safe_value(bytes[i]) {
+                Some(value) => value,
+                None => {
+                    return Err(ParseError::InvalidCharacter {
+                        offset: i,
+                        found: char_at(bytes, i),
+                        source: None,
+                    })
+                }
+            };
+            i += 1;
+        }
+        match uuid_from_symbols(&values) {
+            Some(uuid) => Ok(UuidB64::from_uuid(uuid)),
+            None => Err(ParseError::NonCanonicalTrailingBits {
+                offset: UUID_SYMBOLS - 1,
+                source: None,
+            }),
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn __from_literal(s: &str) -> UuidB64 {
+        match UuidB64::parse_const(s) {
+            Ok(id) => id,
+            Err(ParseError::WrongLength { .. }) => {
+                panic!("uuid_b64! literals must be 22 characters long")
+            }
+            Err(ParseError::InvalidCharacter { .. }) => {
+                panic!("uuid_b64! literals may only contain A-Z, a-z, 0-9, '-' and '_'")
+            }
+            Err(_) => panic!(
+                "the last character of a uuid_b64! literal sets bits that are not part of the ID"
+            ),
+        }
+    }
+}
+
+/// A `UuidB64` constant from a string literal, checked at compile time
+///
+/// The literal must be in the 22 character URL-safe form that `Display`
+/// writes. The macro evaluates to a constant, so it can be used to define
+/// other constants and never parses anything at run time.
+///
+/// ```rust
+/// use uuid_b64::{uuid_b64, UuidB64};
+///
+/// const DEFAULT_ORG: UuidB64 = uuid_b64!("sMHuhm9GTxuNi3hJ51287g");
+/// assert_eq!(DEFAULT_ORG.uuid().to_string(), "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee");
+/// ```
+///
+/// Invalid literals are a compile error:
+///
+/// ```rust,compile_fail
+/// use uuid_b64::{uuid_b64, UuidB64};
+///
+/// const DEFAULT_ORG: UuidB64 = uuid_b64!("sMHuhm9GTx+Ni3hJ51287g");
+/// ```
+#[macro_export]
+macro_rules! uuid_b64 {
+    ($s:literal) => {{
+        const ID: $crate::UuidB64 = $crate::UuidB64::__from_literal($s);
+        ID
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn matches_from_str() {
+        for id in [
+            UuidB64::from(Uuid::nil()),
+            UuidB64::from(Uuid::max()),
+            "sMHuhm9GTxuNi3hJ51287g".parse().unwrap(),
+        ] {
+            let s = id.to_string();
+            assert_eq!(UuidB64::parse_const(&s), Ok(id));
+        }
+        const ID: UuidB64 = uuid_b64!("sMHuhm9GTxuNi3hJ51287g");
+        assert_eq!(ID, "sMHuhm9GTxuNi3hJ51287g".parse().unwrap());
+    }
+
+    #[test]
+    fn same_errors_as_from_str() {
+        for bad in [
+            "",
+            "sMHuhm9GTxuNi3hJ51287",
+            "sMHuhm9GTxuNi3hJ51287g==",
+            "sMHuhm9GTx!Ni3hJ51287g",
+            "sMHuhm9GTx+Ni3hJ51287g",
+            "sMHuhm9GTxéNi3hJ51287",
+            "sMHuhm9GTxuNi3hJ51287h",
+        ] {
+            let expected = match bad.parse::<UuidB64>().unwrap_err() {
+                ParseError::InvalidCharacter { offset, found, .. } => {
+                    ParseError::InvalidCharacter {
+                        offset,
+                        found,
+                        source: None,
+                    }
+                }
+                ParseError::NonCanonicalTrailingBits { offset, .. } => {
+                    ParseError::NonCanonicalTrailingBits {
+                        offset,
+                        source: None,
+                    }
+                }
+                err => err,
+            };
+            assert_eq!(UuidB64::parse_const(bad), Err(expected), "{:?}", bad);
+        }
+    }
+}
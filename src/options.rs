@@ -0,0 +1,244 @@
+//! Configurable strictness for parsing the base64 form
+//!
+//! `FromStr` accepts exactly what `Display` writes, plus the
+//! [grouped](crate::Grouped) form. Input from other systems is often a little
+//! off: padded, wrapped in whitespace, or written with the standard `+` and
+//! `/` symbols instead of the URL-safe `-` and `_`. [`ParseOptions`] lets you
+//! choose which of those to accept.
+
+use crate::encoding::{symbol_for, Encoding, UUID_SYMBOLS};
+use crate::errors::ParseError;
+use crate::{B64Bytes, UuidB64};
+
+/// Which deviations from the canonical form [`UuidB64::parse_with`] accepts
+///
+/// The default is strict: exactly the characters the encoding writes,
+/// including its padding if it has any, with the unused bits of the last
+/// symbol set to zero. Each option relaxes one rule.
+///
+/// ```rust
+/// # use uuid_b64::{ParseOptions, UuidB64};
+/// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+///
+/// let strict = ParseOptions::default();
+/// assert!(<UuidB64>::parse_with(" sMHuhm9GTxuNi3hJ51287g==\n", strict).is_err());
+///
+/// let lenient = ParseOptions::lenient();
+/// assert_eq!(UuidB64::parse_with(" sMHuhm9GTxuNi3hJ51287g==\n", lenient).unwrap(), id);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    padding: bool,
+    whitespace: bool,
+    standard_alphabet: bool,
+}
+
+impl ParseOptions {
+    /// Accept only the canonical form, the same as `ParseOptions::default()`
+    pub const fn strict() -> ParseOptions {
+        ParseOptions {
+            padding: false,
+            whitespace: false,
+            standard_alphabet: false,
+        }
+    }
+
+    /// Accept every deviation these options know about
+    pub const fn lenient() -> ParseOptions {
+        ParseOptions {
+            padding: true,
+            whitespace: true,
+            standard_alphabet: true,
+        }
+    }
+
+    /// Accept the string with or without `==` padding, whichever the
+    /// encoding writes
+    pub const fn allow_padding(mut self, allow: bool) -> ParseOptions {
+        self.padding = allow;
+        self
+    }
+
+    /// Ignore whitespace before and after the ID
+    pub const fn allow_whitespace(mut self, allow: bool) -> ParseOptions {
+        self.whitespace = allow;
+        self
+    }
+
+    /// Accept `+` and `/` for the symbols the standard alphabet uses them
+    /// for, as well as the encoding's own symbols
+    pub const fn allow_standard_alphabet(mut self, allow: bool) -> ParseOptions {
+        self.standard_alphabet = allow;
+        self
+    }
+}
+
+impl<E: Encoding> UuidB64<E> {
+    /// Parse the base64 form, accepting the deviations allowed by `options`
+    ///
+    /// See [`ParseOptions`] for an example.
+    pub fn parse_with(s: &str, options: ParseOptions) -> Result<Self, ParseError> {
+        let (start, trimmed) = if options.whitespace {
+            let trimmed = s.trim();
+            (s.len() - s.trim_start().len(), trimmed)
+        } else {
+            (0, s)
+        };
+        let len = B64Bytes::<16, E>::encoded_len();
+        let found = trimmed.len();
+        if found != len
+            && !(options.padding && (found == UUID_SYMBOLS || found == UUID_SYMBOLS + 2))
+        {
+            return Err(ParseError::WrongLength {
+                expected: len,
+                found,
+            });
+        }
+        let (symbols, padding) = trimmed.as_bytes().split_at(UUID_SYMBOLS);
+        if !padding.is_empty() && padding != b"==" {
+            return Err(ParseError::InvalidPadding { source: None });
+        }
+
+        // rewrite the input into the canonical form, so that the engine
+        // decodes it and reports any errors
+        let mut canonical = [b'='; UUID_SYMBOLS + 2];
+        let mut offsets = [0; UUID_SYMBOLS + 2];
+        for (i, (symbol, &c)) in canonical.iter_mut().zip(symbols).enumerate() {
+            *symbol = match c {
+                b'+' if options.standard_alphabet => symbol_for::<E>(62),
+                b'/' if options.standard_alphabet => symbol_for::<E>(63),
+                c => c,
+            };
+            offsets[i] = start + i;
+        }
+        B64Bytes::decode_symbols(s, &canonical[..len], &offsets).map(UuidB64::from_b64_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::Standard;
+
+    #[test]
+    fn strict_by_default() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let strict = ParseOptions::default();
+        assert_eq!(strict, ParseOptions::strict());
+        assert_eq!(
+            UuidB64::parse_with("sMHuhm9GTxuNi3hJ51287g", strict).unwrap(),
+            id
+        );
+        for bad in [
+            // short, long, non-canonical trailing bits, padding, whitespace
+            // and the standard alphabet
+            "sMHuhm9G",
+            "sMHuhm9GTxuNi3hJ51287gA",
+            "sMHuhm9GTxuNi3hJ51287h",
+            "sMHuhm9GTxuNi3hJ51287g==",
+            " sMHuhm9GTxuNi3hJ51287g",
+            "+8Huhm9GTxuNi3hJ51287g",
+        ] {
+            assert!(<UuidB64>::parse_with(bad, strict).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn each_option_relaxes_one_rule() {
+        let id: UuidB64 = "-8Huhm9GTxuNi3hJ51287g".parse().unwrap();
+        let strict = ParseOptions::strict();
+
+        let padding = strict.allow_padding(true);
+        assert_eq!(
+            UuidB64::parse_with("-8Huhm9GTxuNi3hJ51287g==", padding).unwrap(),
+            id
+        );
+        assert!(<UuidB64>::parse_with("\t-8Huhm9GTxuNi3hJ51287g", padding).is_err());
+
+        let whitespace = strict.allow_whitespace(true);
+        assert_eq!(
+            UuidB64::parse_with("\t-8Huhm9GTxuNi3hJ51287g\n", whitespace).unwrap(),
+            id
+        );
+        assert!(<UuidB64>::parse_with("+8Huhm9GTxuNi3hJ51287g", whitespace).is_err());
+
+        let standard = strict.allow_standard_alphabet(true);
+        assert_eq!(
+            UuidB64::parse_with("+8Huhm9GTxuNi3hJ51287g", standard).unwrap(),
+            id
+        );
+        assert!(<UuidB64>::parse_with("+8Huhm9GTxuNi3hJ51287g==", standard).is_err());
+
+        // no option allows a short string or non-canonical bits
+        let lenient = ParseOptions::lenient();
+        assert!(<UuidB64>::parse_with("-8Huhm9G", lenient).is_err());
+        assert!(<UuidB64>::parse_with("-8Huhm9GTxuNi3hJ51287h", lenient).is_err());
+    }
+
+    #[test]
+    fn errors_are_relative_to_the_input() {
+        let whitespace = ParseOptions::strict().allow_whitespace(true);
+        assert!(matches!(
+            <UuidB64>::parse_with("  -8Huhm9GTx!Ni3hJ51287g", whitespace),
+            Err(ParseError::InvalidCharacter {
+                offset: 12,
+                found: '!',
+                ..
+            })
+        ));
+        assert!(matches!(
+            <UuidB64>::parse_with("  -8Huhm9GTxuNi3hJ51287h", whitespace),
+            Err(ParseError::NonCanonicalTrailingBits { offset: 23, .. })
+        ));
+        assert_eq!(
+            <UuidB64>::parse_with("-8Huhm9GTxuNi3hJ51287g==", ParseOptions::strict()),
+            Err(ParseError::WrongLength {
+                expected: 22,
+                found: 24
+            })
+        );
+        assert!(matches!(
+            <UuidB64>::parse_with("-8Huhm9GTxuNi3hJ51287gAA", ParseOptions::lenient()),
+            Err(ParseError::InvalidPadding { .. })
+        ));
+    }
+
+    #[test]
+    fn standard_symbols_follow_the_encoding() {
+        use crate::encoding::Sortable;
+        use uuid::Uuid;
+
+        let max = UuidB64::<Sortable>::from_uuid(Uuid::max());
+        assert_eq!(max.to_string(), "zzzzzzzzzzzzzzzzzzzzzk");
+        let standard = ParseOptions::strict().allow_standard_alphabet(true);
+        assert_eq!(
+            UuidB64::parse_with("/////////////////////k", standard).unwrap(),
+            max
+        );
+        assert!(matches!(
+            <UuidB64>::parse_with("sMHuhm9GTxuNi3hJ5128é", ParseOptions::lenient()),
+            Err(ParseError::InvalidCharacter {
+                offset: 20,
+                found: 'é',
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn follows_padded_encodings() {
+        let id = "sMHuhm9GTxuNi3hJ51287g"
+            .parse::<UuidB64>()
+            .unwrap()
+            .with_encoding::<Standard>();
+        let padded = id.to_string();
+        let unpadded = padded.trim_end_matches('=');
+        let strict = ParseOptions::strict();
+        assert_eq!(UuidB64::parse_with(&padded, strict).unwrap(), id);
+        assert!(UuidB64::<Standard>::parse_with(unpadded, strict).is_err());
+        assert_eq!(
+            UuidB64::parse_with(unpadded, strict.allow_padding(true)).unwrap(),
+            id
+        );
+    }
+}
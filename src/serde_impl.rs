@@ -1,37 +1,60 @@
 extern crate serde;
 
-use std::fmt::{Formatter, Result as FmtResult};
+use core::fmt::{Formatter, Result as FmtResult};
+use core::marker::PhantomData;
 
 use self::serde::de::{self, Deserialize, Deserializer, Visitor};
 use self::serde::ser::{Serialize, Serializer};
 
-use super::UuidB64;
+use crate::encoding::Encoding;
 
-impl Serialize for UuidB64 {
+use super::{
+    B64Bytes, UuidB64, UuidB64V1, UuidB64V3, UuidB64V4, UuidB64V5, UuidB64V6, UuidB64V7, UuidB64V8,
+};
+
+impl<const N: usize, Enc: Encoding> Serialize for B64Bytes<N, Enc> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_stack_str())
+    }
+}
+
+impl<'de, const N: usize, Enc: Encoding> Deserialize<'de> for B64Bytes<N, Enc> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(B64BytesVisitor(PhantomData))
+    }
+}
+
+impl<Enc: Encoding> Serialize for UuidB64<Enc> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.to_istring())
+        self.as_b64_bytes().serialize(serializer)
     }
 }
 
-impl<'de> Deserialize<'de> for UuidB64 {
+impl<'de, Enc: Encoding> Deserialize<'de> for UuidB64<Enc> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(UuidB64Visitor)
+        B64Bytes::deserialize(deserializer).map(UuidB64::from_b64_bytes)
     }
 }
 
-struct UuidB64Visitor;
+struct B64BytesVisitor<const N: usize, Enc>(PhantomData<Enc>);
 
-impl<'de> Visitor<'de> for UuidB64Visitor {
-    type Value = UuidB64;
+impl<'de, const N: usize, Enc: Encoding> Visitor<'de> for B64BytesVisitor<N, Enc> {
+    type Value = B64Bytes<N, Enc>;
 
     fn expecting(&self, f: &mut Formatter) -> FmtResult {
-        write!(f, "a URL-safe Base64-encoded string")
+        write!(f, "a Base64-encoded string")
     }
 
     fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
@@ -42,13 +65,121 @@ impl<'de> Visitor<'de> for UuidB64Visitor {
     }
 }
 
+/// Deserializes any of the version-checked wrappers via their `FromStr`
+struct VersionedVisitor<T>(PhantomData<T>);
+
+macro_rules! versioned_serde {
+    ($($name:ident),*) => {$(
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.uuid_b64().serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str(VersionedVisitor::<$name>(PhantomData))
+            }
+        }
+
+        impl<'de> Visitor<'de> for VersionedVisitor<$name> {
+            type Value = $name;
+
+            fn expecting(&self, f: &mut Formatter) -> FmtResult {
+                write!(
+                    f,
+                    "a URL-safe Base64-encoded version {} UUID",
+                    $name::VERSION
+                )
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                s.parse().map_err(de::Error::custom)
+            }
+        }
+    )*};
+}
+
+versioned_serde!(UuidB64V1, UuidB64V3, UuidB64V4, UuidB64V5, UuidB64V6, UuidB64V7, UuidB64V8);
+
+/// Deserialize a `UuidB64` from any of the forms [`UuidB64::parse_any`] accepts
+///
+/// Use it with `#[serde(with = "uuid_b64::any_format")]`. Serializing writes
+/// the usual base64 form.
+///
+/// ```rust
+/// # use serde_derive::Deserialize;
+/// # use uuid_b64::UuidB64;
+/// #[derive(Deserialize)]
+/// struct Order {
+///     #[serde(with = "uuid_b64::any_format")]
+///     id: UuidB64,
+/// }
+///
+/// let order: Order =
+///     serde_json::from_str(r#"{"id": "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"}"#).unwrap();
+/// assert_eq!(order.id.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+/// ```
+pub mod any_format {
+    use core::fmt::{Formatter, Result as FmtResult};
+
+    use super::serde::de::{self, Deserializer, Visitor};
+    use super::serde::ser::{Serialize, Serializer};
+
+    use crate::UuidB64;
+
+    /// Serialize as the usual base64 form
+    pub fn serialize<S>(id: &UuidB64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        id.serialize(serializer)
+    }
+
+    /// Deserialize from any form [`UuidB64::parse_any`] accepts
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<UuidB64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(AnyFormatVisitor)
+    }
+
+    struct AnyFormatVisitor;
+
+    impl<'de> Visitor<'de> for AnyFormatVisitor {
+        type Value = UuidB64;
+
+        fn expecting(&self, f: &mut Formatter) -> FmtResult {
+            write!(f, "a Base64-encoded, hex or URN UUID")
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            UuidB64::parse_any(s)
+                .map(|(id, _)| id)
+                .map_err(de::Error::custom)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_derive::Deserialize;
     use serde_json::json;
     use uuid::Uuid;
 
-    use crate::UuidB64;
+    use crate::{B64Bytes, UuidB64};
 
     #[test]
     fn ser_de() {
@@ -68,4 +199,88 @@ mod tests {
 
         assert_eq!(mything.myid, my_id);
     }
+
+    #[test]
+    fn ser_de_bytes() {
+        let hash = B64Bytes::<32>::from([0xab; 32]);
+        let json = json!({ "hash": hash }).to_string();
+        assert_eq!(
+            json,
+            r#"{"hash":"q6urq6urq6urq6urq6urq6urq6urq6urq6urq6urq6s"}"#
+        );
+
+        #[derive(Deserialize)]
+        struct TestThing {
+            hash: B64Bytes<32>,
+        }
+
+        let mything: TestThing = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(mything.hash, hash);
+        assert!(::serde_json::from_str::<TestThing>(r#"{"hash":"q6urq6ur"}"#).is_err());
+    }
+
+    #[test]
+    fn any_format_accepts_hyphenated() {
+        #[derive(Debug, Deserialize)]
+        struct TestThing {
+            #[serde(with = "crate::any_format")]
+            myid: UuidB64,
+        }
+
+        let uuid = Uuid::from_fields(0xff, 2, 3, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        for json in [
+            json!({ "myid": "AAAA_wACAAMBAgMEBQYHCA" }),
+            json!({ "myid": uuid.hyphenated().to_string() }),
+            json!({ "myid": uuid.urn().to_string() }),
+        ] {
+            let mything: TestThing = ::serde_json::from_value(json).unwrap();
+            assert_eq!(mything.myid, UuidB64::from(uuid));
+        }
+        let err = ::serde_json::from_value::<TestThing>(json!({ "myid": "nope" })).unwrap_err();
+        assert!(
+            err.to_string().contains("expected 22 characters, found 4"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn follows_encoding() {
+        use crate::encoding::Standard;
+
+        let my_id: UuidB64<Standard> =
+            UuidB64::from(Uuid::from_fields(0xff, 2, 3, &[1, 2, 3, 4, 5, 6, 7, 0xff]))
+                .with_encoding();
+        let json = json!({ "myid": my_id }).to_string();
+        assert_eq!(json, r#"{"myid":"AAAA/wACAAMBAgMEBQYH/w=="}"#);
+
+        #[derive(Deserialize)]
+        struct TestThing {
+            myid: UuidB64<Standard>,
+        }
+
+        let mything: TestThing = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(mything.myid, my_id);
+    }
+
+    #[test]
+    fn versioned_de_checks_version() {
+        use crate::{UuidB64V4, UuidB64V7};
+
+        #[derive(Debug, Deserialize)]
+        struct TestThing {
+            myid: UuidB64V7,
+        }
+
+        let v7 = UuidB64V7::new();
+        let json = json!({ "myid": v7 }).to_string();
+        let mything: TestThing = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(mything.myid, v7);
+
+        let json = json!({ "myid": UuidB64V4::new() }).to_string();
+        let err = ::serde_json::from_str::<TestThing>(&json).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected a version 7 UUID, found version 4"));
+    }
 }
@@ -0,0 +1,363 @@
+//! Speakable forms of a `UuidB64`, for incident calls and printed labels
+//!
+//! Two forms are supported:
+//!
+//! * [Proquints][], which spell every 16 bits as a pronounceable five letter
+//!   consonant-vowel-consonant-vowel-consonant syllable.
+//! * A mnemonic of 16 words from a fixed list of 256, one word per byte. The
+//!   words are common, concrete nouns that differ from each other in at
+//!   least two letters, so the parser can suggest the intended word when one
+//!   is misheard or mistyped.
+//!
+//! [Proquints]: https://arxiv.org/html/0901.4016
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use uuid::Uuid;
+
+use crate::errors::ParseError;
+use crate::UuidB64;
+
+const CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+const VOWELS: &[u8; 4] = b"aiou";
+
+/// The mnemonic word list, sorted so it can be binary searched
+const WORDS: [&str; 256] = [
+    "acid", "acorn", "actor", "adobe", "agent", "alarm", "album", "anchor", "angle", "apple",
+    "apron", "arena", "arrow", "atlas", "attic", "autumn", "badge", "bagel", "baker", "balloon",
+    "bamboo", "banjo", "barrel", "basil", "beaver", "bench", "berry", "bison", "blanket", "bonnet",
+    "border", "bottle", "branch", "bread", "brick", "bridge", "broom", "bucket", "buffalo",
+    "bunny", "butter", "button", "cabin", "cactus", "camel", "candle", "canoe", "canyon", "carpet",
+    "carrot", "castle", "cedar", "cello", "cereal", "chalk", "cherry", "chess", "cider", "circus",
+    "clover", "cobra", "cocoa", "comet", "copper", "coral", "cotton", "cougar", "cowboy", "coyote",
+    "crater", "cricket", "crystal", "cupcake", "curtain", "daisy", "dancer", "delta", "denim",
+    "desert", "diamond", "dinner", "doctor", "dolphin", "dragon", "drum", "eagle", "easel", "echo",
+    "eclipse", "elbow", "ember", "empire", "engine", "falcon", "feather", "fence", "fiddle",
+    "finch", "fjord", "flag", "flute", "forest", "fossil", "galaxy", "garden", "garlic", "gecko",
+    "geyser", "ginger", "giraffe", "glacier", "goblet", "gopher", "gorilla", "granite", "gravel",
+    "guitar", "hammer", "harbor", "harvest", "hazel", "helmet", "heron", "hockey", "honey",
+    "hornet", "hotel", "igloo", "iguana", "island", "ivory", "jacket", "jaguar", "jasmine",
+    "jelly", "jersey", "jigsaw", "jungle", "kayak", "kernel", "kettle", "kitten", "koala",
+    "ladder", "lagoon", "lantern", "lava", "lemon", "leopard", "lettuce", "lily", "lizard",
+    "lobster", "lotus", "lumber", "magnet", "mango", "maple", "marble", "meadow", "melon",
+    "mirror", "monkey", "moose", "mosaic", "muffin", "museum", "napkin", "nectar", "needle",
+    "noodle", "nutmeg", "oasis", "ocean", "olive", "onion", "orange", "orchid", "otter", "oyster",
+    "paddle", "palace", "panda", "papaya", "parade", "peanut", "pebble", "pelican", "pepper",
+    "piano", "pickle", "pigeon", "pillow", "pirate", "planet", "plum", "pocket", "polar", "poppy",
+    "potato", "pretzel", "pumpkin", "puzzle", "python", "quarry", "quartz", "quilt", "rabbit",
+    "radar", "radish", "raisin", "raven", "record", "ribbon", "river", "robin", "salmon", "satin",
+    "sausage", "scarf", "shadow", "shovel", "silver", "sketch", "sparrow", "spider", "spinach",
+    "squid", "statue", "stereo", "summit", "sunset", "surfer", "tablet", "teapot", "tennis",
+    "thunder", "tiger", "tomato", "tractor", "trumpet", "tulip", "tunnel", "turtle", "tuxedo",
+    "unicorn", "valley", "velvet", "violin", "volcano", "waffle", "walnut", "walrus", "yogurt",
+    "zebra", "zipper",
+];
+
+/// Displays a `UuidB64` as eight proquints, created by [`UuidB64::proquint`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Proquint(u128);
+
+impl Display for Proquint {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for i in 0..8 {
+            let word = (self.0 >> (112 - 16 * i)) as u16;
+            if i > 0 {
+                f.write_str("-")?;
+            }
+            let quint = [
+                CONSONANTS[usize::from(word >> 12)],
+                VOWELS[usize::from(word >> 10) & 0x3],
+                CONSONANTS[usize::from(word >> 6) & 0xf],
+                VOWELS[usize::from(word >> 4) & 0x3],
+                CONSONANTS[usize::from(word) & 0xf],
+            ];
+            f.write_str(core::str::from_utf8(&quint).unwrap())?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays a `UuidB64` as 16 words, created by [`UuidB64::mnemonic`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Mnemonic([u8; 16]);
+
+impl Display for Mnemonic {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(WORDS[usize::from(*byte)])?;
+        }
+        Ok(())
+    }
+}
+
+/// The longest word in the mnemonic list
+const MAX_WORD_LEN: usize = 8;
+
+fn is_separator(c: char) -> bool {
+    c == '-' || c.is_whitespace()
+}
+
+/// The words in `s` with the byte offset each one starts at
+fn words(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.split(is_separator)
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - s.as_ptr() as usize, word))
+}
+
+/// Decode the proquint starting at byte `offset` of `s`
+fn decode_quint(s: &str, offset: usize, quint: &str) -> Result<u16, ParseError> {
+    let mut value = 0;
+    for (i, c) in quint.bytes().enumerate().take(5) {
+        let (symbols, bits): (&[u8], u32) = if i % 2 == 0 {
+            (CONSONANTS, 4)
+        } else {
+            (VOWELS, 2)
+        };
+        let symbol = symbols
+            .iter()
+            .position(|&x| x == c.to_ascii_lowercase())
+            .ok_or_else(|| ParseError::invalid_character(s, offset + i))?;
+        value = value << bits | symbol as u16;
+    }
+    match quint.len() {
+        5 => Ok(value),
+        // a sixth letter, or a separator where a letter should be
+        len if offset + len.min(5) < s.len() => {
+            Err(ParseError::invalid_character(s, offset + len.min(5)))
+        }
+        len => Err(ParseError::WrongLength {
+            expected: s.len() + 5 - len,
+            found: s.len(),
+        }),
+    }
+}
+
+/// The number of single-character edits needed to turn `a` into `b`,
+/// ignoring case
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = &b.as_bytes()[..b.len().min(MAX_WORD_LEN)];
+    let mut row = [0; MAX_WORD_LEN + 1];
+    for (j, cell) in row.iter_mut().enumerate() {
+        *cell = j;
+    }
+    for (i, ca) in a.bytes().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            let substitution = usize::from(!ca.eq_ignore_ascii_case(&cb));
+            row[j + 1] = (above + 1).min(row[j] + 1).min(diagonal + substitution);
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// The word from the mnemonic list that is closest to `word`
+fn closest_word(word: &str) -> &'static str {
+    WORDS
+        .iter()
+        .min_by_key(|candidate| edit_distance(word, candidate))
+        .unwrap()
+}
+
+impl<E> UuidB64<E> {
+    /// Display this ID as eight proquints separated by `-`
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let spoken = id.proquint().to_string();
+    /// assert_eq!(spoken, "ragad-vupak-kutak-husir-mukar-lodan-vitit-rugov");
+    /// assert_eq!(UuidB64::parse_proquint(&spoken).unwrap(), id);
+    /// ```
+    pub fn proquint(&self) -> Proquint {
+        Proquint(self.uuid().as_u128())
+    }
+
+    /// Display this ID as 16 words separated by spaces
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let spoken = id.mnemonic().to_string();
+    /// assert_eq!(UuidB64::parse_mnemonic(&spoken).unwrap(), id);
+    /// ```
+    pub fn mnemonic(&self) -> Mnemonic {
+        Mnemonic(*self.0.as_bytes())
+    }
+}
+
+impl UuidB64 {
+    /// Parse eight proquints, as written by [`UuidB64::proquint`]
+    ///
+    /// The proquints may be separated by `-` or whitespace, and case is
+    /// ignored.
+    pub fn parse_proquint(s: &str) -> Result<UuidB64, ParseError> {
+        let mut quints = words(s);
+        let mut value: u128 = 0;
+        for i in 0..8 {
+            let (offset, quint) = quints.next().ok_or(ParseError::WrongWordCount {
+                expected: 8,
+                found: i,
+            })?;
+            value = value << 16 | u128::from(decode_quint(s, offset, quint)?);
+        }
+        let extra = quints.count();
+        if extra > 0 {
+            return Err(ParseError::WrongWordCount {
+                expected: 8,
+                found: 8 + extra,
+            });
+        }
+        Ok(UuidB64::from(Uuid::from_u128(value)))
+    }
+
+    /// Parse 16 words, as written by [`UuidB64::mnemonic`]
+    ///
+    /// The words may be separated by `-` or whitespace, and case is ignored.
+    /// If a word is not in the list the error includes the closest word that
+    /// is:
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let spoken = "acid acid acid acid acid acid acid acid \
+    ///               acid acid acid acid acid acid acid zebr";
+    /// let err = UuidB64::parse_mnemonic(spoken).unwrap_err();
+    /// assert_eq!(err.to_string(), "word 16 is not in the word list, did you mean 'zebra'?");
+    /// ```
+    pub fn parse_mnemonic(s: &str) -> Result<UuidB64, ParseError> {
+        let mut words = words(s);
+        let mut bytes = [0; 16];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            let (_, word) = words.next().ok_or(ParseError::WrongWordCount {
+                expected: 16,
+                found: index,
+            })?;
+            let lowercase = word.bytes().map(|c| c.to_ascii_lowercase());
+            *byte = WORDS
+                .binary_search_by(|candidate| candidate.bytes().cmp(lowercase.clone()))
+                .map_err(|_| ParseError::UnknownWord {
+                    index,
+                    suggestion: closest_word(word),
+                })? as u8;
+        }
+        let extra = words.count();
+        if extra > 0 {
+            return Err(ParseError::WrongWordCount {
+                expected: 16,
+                found: 16 + extra,
+            });
+        }
+        Ok(UuidB64::from(Uuid::from_bytes(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_list_is_sorted_and_distinct() {
+        assert!(WORDS.iter().all(|word| word.len() <= MAX_WORD_LEN));
+        for pair in WORDS.windows(2) {
+            assert!(pair[0] < pair[1], "{} >= {}", pair[0], pair[1]);
+        }
+        for (i, a) in WORDS.iter().enumerate() {
+            for b in &WORDS[i + 1..] {
+                assert!(edit_distance(a, b) >= 2, "{} and {} are too similar", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn proquint_matches_reference() {
+        // from the proquint paper: 127.0.0.1 is lusab-babad
+        let id = UuidB64::from(Uuid::from_u128(0x7f00_0001 << 96));
+        assert!(id.proquint().to_string().starts_with("lusab-babad-babab-"));
+    }
+
+    #[test]
+    fn roundtrips() {
+        for id in [
+            UuidB64::from(Uuid::nil()),
+            UuidB64::from(Uuid::max()),
+            "sMHuhm9GTxuNi3hJ51287g".parse().unwrap(),
+        ] {
+            let proquint = id.proquint().to_string();
+            assert_eq!(UuidB64::parse_proquint(&proquint).unwrap(), id);
+            assert_eq!(
+                UuidB64::parse_proquint(&proquint.to_uppercase().replace('-', " ")).unwrap(),
+                id
+            );
+
+            let mnemonic = id.mnemonic().to_string();
+            assert_eq!(mnemonic.split(' ').count(), 16);
+            assert_eq!(UuidB64::parse_mnemonic(&mnemonic).unwrap(), id);
+            assert_eq!(
+                UuidB64::parse_mnemonic(&mnemonic.to_uppercase().replace(' ', "-")).unwrap(),
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!(
+            UuidB64::parse_proquint("lusab-babad"),
+            Err(ParseError::WrongWordCount {
+                expected: 8,
+                found: 2
+            })
+        );
+        assert!(matches!(
+            UuidB64::parse_proquint("lusab-babad-babab-babab-babab-babab-babab-babaa"),
+            Err(ParseError::InvalidCharacter {
+                offset: 46,
+                found: 'a',
+                ..
+            })
+        ));
+        assert!(matches!(
+            UuidB64::parse_proquint("lusab-babad-babab-babab-babab-babab-babab-bab"),
+            Err(ParseError::WrongLength { .. })
+        ));
+        assert!(matches!(
+            UuidB64::parse_proquint("lusab-babadd-babab-babab-babab-babab-babab-babab"),
+            Err(ParseError::InvalidCharacter {
+                offset: 11,
+                found: 'd',
+                ..
+            })
+        ));
+        let nine = "lusab-babad-babab-babab-babab-babab-babab-babab-babab";
+        assert_eq!(
+            UuidB64::parse_proquint(nine),
+            Err(ParseError::WrongWordCount {
+                expected: 8,
+                found: 9
+            })
+        );
+
+        assert!(matches!(
+            UuidB64::parse_mnemonic("acid acid"),
+            Err(ParseError::WrongWordCount { found: 2, .. })
+        ));
+        assert!(matches!(
+            UuidB64::parse_mnemonic(&["acid"; 17].join(" ")),
+            Err(ParseError::WrongWordCount { found: 17, .. })
+        ));
+        let mut typo = ["acid"; 16];
+        typo[3] = "KITEN";
+        assert_eq!(
+            UuidB64::parse_mnemonic(&typo.join(" ")),
+            Err(ParseError::UnknownWord {
+                index: 3,
+                suggestion: "kitten"
+            })
+        );
+    }
+}
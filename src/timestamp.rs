@@ -0,0 +1,335 @@
+//! Creation times embedded in v1, v6 and v7 UUIDs
+
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use uuid::timestamp::UUID_TICKS_BETWEEN_EPOCHS;
+use uuid::{Uuid, Version};
+
+use crate::UuidB64;
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// The creation time embedded in a v1, v6 or v7 UUID
+///
+/// v1 and v6 UUIDs count 100ns ticks since the start of the Gregorian
+/// calendar, v7 UUIDs count milliseconds since the Unix epoch; this type holds
+/// either as a signed offset from the Unix epoch so that they can be compared
+/// with each other.
+///
+/// ```rust
+/// # use std::time::SystemTime;
+/// # use uuid_b64::UuidB64;
+/// # #[cfg(all(feature = "std", feature = "rng"))]
+/// # fn main() {
+/// let id = UuidB64::new_v7();
+/// let created = SystemTime::try_from(id.timestamp().unwrap()).unwrap();
+/// assert!(created <= SystemTime::now());
+///
+/// assert!(UuidB64::new().timestamp().is_none());
+/// # }
+/// # #[cfg(not(all(feature = "std", feature = "rng")))]
+/// # fn main() {}
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UuidTimestamp {
+    seconds: i64,
+    subsec_nanos: u32,
+}
+
+impl UuidTimestamp {
+    pub(crate) fn from_uuid(id: &Uuid) -> Option<UuidTimestamp> {
+        let ts = id.get_timestamp()?;
+        match id.get_version() {
+            Some(Version::Mac) | Some(Version::SortMac) => {
+                let (ticks, _) = ts.to_gregorian();
+                let nanos = (i128::from(ticks) - i128::from(UUID_TICKS_BETWEEN_EPOCHS)) * 100;
+                Some(UuidTimestamp::from_unix_nanos(nanos))
+            }
+            _ => {
+                let (seconds, subsec_nanos) = ts.to_unix();
+                Some(UuidTimestamp {
+                    seconds: seconds as i64,
+                    subsec_nanos,
+                })
+            }
+        }
+    }
+
+    fn from_unix_nanos(nanos: i128) -> UuidTimestamp {
+        UuidTimestamp {
+            seconds: nanos.div_euclid(NANOS_PER_SEC) as i64,
+            subsec_nanos: nanos.rem_euclid(NANOS_PER_SEC) as u32,
+        }
+    }
+
+    /// Seconds since the Unix epoch, negative for times before 1970
+    pub fn unix_seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// Nanoseconds past [`unix_seconds`](UuidTimestamp::unix_seconds), always positive
+    pub fn subsec_nanos(&self) -> u32 {
+        self.subsec_nanos
+    }
+
+    /// Nanoseconds since the Unix epoch, negative for times before 1970
+    pub fn unix_nanos(&self) -> i128 {
+        i128::from(self.seconds) * NANOS_PER_SEC + i128::from(self.subsec_nanos)
+    }
+}
+
+/// Convert days since the Unix epoch into a proleptic Gregorian (year, month, day)
+///
+/// This is Howard Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+impl Display for UuidTimestamp {
+    /// Write the timestamp as an RFC 3339 UTC date and time
+    ///
+    /// Trailing zeroes in the fractional seconds are left off.
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let (year, month, day) = civil_from_days(self.seconds.div_euclid(86_400));
+        let secs_of_day = self.seconds.rem_euclid(86_400);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60
+        )?;
+        if self.subsec_nanos != 0 {
+            let mut nanos = self.subsec_nanos;
+            let mut width = 9;
+            while nanos.is_multiple_of(10) {
+                nanos /= 10;
+                width -= 1;
+            }
+            write!(f, ".{:0width$}", nanos, width = width)?;
+        }
+        write!(f, "Z")
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<UuidTimestamp> for SystemTime {
+    type Error = crate::errors::TimestampOutOfRange;
+
+    fn try_from(ts: UuidTimestamp) -> Result<Self, Self::Error> {
+        let since_epoch = Duration::new(ts.seconds.unsigned_abs(), 0);
+        if ts.seconds >= 0 {
+            UNIX_EPOCH.checked_add(since_epoch)
+        } else {
+            UNIX_EPOCH.checked_sub(since_epoch)
+        }
+        .and_then(|time| time.checked_add(Duration::from_nanos(u64::from(ts.subsec_nanos))))
+        .ok_or(crate::errors::TimestampOutOfRange)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<UuidTimestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = crate::errors::TimestampOutOfRange;
+
+    fn try_from(ts: UuidTimestamp) -> Result<Self, Self::Error> {
+        chrono::DateTime::from_timestamp(ts.seconds, ts.subsec_nanos)
+            .ok_or(crate::errors::TimestampOutOfRange)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<UuidTimestamp> for time::OffsetDateTime {
+    type Error = crate::errors::TimestampOutOfRange;
+
+    fn try_from(ts: UuidTimestamp) -> Result<Self, Self::Error> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(ts.unix_nanos())
+            .map_err(|_| crate::errors::TimestampOutOfRange)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl TryFrom<UuidTimestamp> for jiff::Timestamp {
+    type Error = crate::errors::TimestampOutOfRange;
+
+    fn try_from(ts: UuidTimestamp) -> Result<Self, Self::Error> {
+        jiff::Timestamp::new(ts.seconds, ts.subsec_nanos as i32)
+            .map_err(|_| crate::errors::TimestampOutOfRange)
+    }
+}
+
+/// Orders IDs by their embedded creation time instead of their bytes
+///
+/// The derived `Ord` on `UuidB64` compares raw bytes. That happens to match
+/// creation order for v6 and v7 IDs, but v1 IDs store the low bits of their
+/// timestamp first, so their byte order is essentially random. Wrap IDs in
+/// `ByTimestamp` to sort them by when they were created. IDs with the same
+/// timestamp are ordered by their bytes, and IDs without a timestamp sort
+/// before all IDs that have one.
+///
+/// ```rust
+/// # use uuid_b64::{ByTimestamp, UuidB64};
+/// # #[cfg(all(feature = "std", feature = "rng"))]
+/// # fn main() {
+/// let mut ids = vec![UuidB64::new_v7(), UuidB64::new_v7(), UuidB64::new_v7()];
+/// let created_order = ids.clone();
+/// ids.reverse();
+/// ids.sort_by_key(|id| ByTimestamp(*id));
+/// assert_eq!(ids, created_order);
+/// # }
+/// # #[cfg(not(all(feature = "std", feature = "rng")))]
+/// # fn main() {}
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ByTimestamp(pub UuidB64);
+
+impl Ord for ByTimestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0.timestamp(), self.0).cmp(&(other.0.timestamp(), other.0))
+    }
+}
+
+impl PartialOrd for ByTimestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<UuidB64> for ByTimestamp {
+    fn from(id: UuidB64) -> ByTimestamp {
+        ByTimestamp(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::{Builder, NoContext, Timestamp};
+
+    use super::*;
+
+    fn v1_at(seconds: u64, nanos: u32) -> UuidB64 {
+        UuidB64::from(Uuid::new_v1(
+            Timestamp::from_unix(NoContext, seconds, nanos),
+            &[1, 2, 3, 4, 5, 6],
+        ))
+    }
+
+    #[test]
+    fn extracts_timestamps() {
+        let v1 = v1_at(1_700_000_000, 123_456_700).timestamp().unwrap();
+        assert_eq!(
+            (v1.unix_seconds(), v1.subsec_nanos()),
+            (1_700_000_000, 123_456_700)
+        );
+
+        let v6 = UuidB64::from(Uuid::new_v6(
+            Timestamp::from_unix(NoContext, 1_700_000_000, 5_000),
+            &[1, 2, 3, 4, 5, 6],
+        ));
+        let v6 = v6.timestamp().unwrap();
+        assert_eq!(
+            (v6.unix_seconds(), v6.subsec_nanos()),
+            (1_700_000_000, 5_000)
+        );
+
+        let v7 = UuidB64::from(
+            Builder::from_unix_timestamp_millis(1_700_000_000_987, &[0; 10]).into_uuid(),
+        );
+        let v7 = v7.timestamp().unwrap();
+        assert_eq!(
+            (v7.unix_seconds(), v7.subsec_nanos()),
+            (1_700_000_000, 987_000_000)
+        );
+
+        let v4: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(v4.timestamp(), None);
+    }
+
+    #[test]
+    fn v1_before_the_unix_epoch() {
+        // 1960-01-01T00:00:00Z, 3652 days before the epoch
+        let ticks = UUID_TICKS_BETWEEN_EPOCHS - 3652 * 86_400 * 10_000_000;
+        let id = UuidB64::from(Uuid::new_v1(
+            Timestamp::from_gregorian_time(ticks, 0),
+            &[1, 2, 3, 4, 5, 6],
+        ));
+        let ts = id.timestamp().unwrap();
+        assert_eq!(ts.unix_seconds(), -3652 * 86_400);
+        #[cfg(feature = "std")]
+        assert_eq!(
+            SystemTime::try_from(ts),
+            Ok(UNIX_EPOCH - Duration::from_secs(3652 * 86_400))
+        );
+    }
+
+    #[test]
+    fn displays_as_rfc3339() {
+        let ts = |seconds, subsec_nanos| UuidTimestamp {
+            seconds,
+            subsec_nanos,
+        };
+        assert_eq!(ts(0, 0).to_string(), "1970-01-01T00:00:00Z");
+        assert_eq!(ts(1_700_000_000, 0).to_string(), "2023-11-14T22:13:20Z");
+        assert_eq!(
+            ts(951_782_400, 120_000_000).to_string(),
+            "2000-02-29T00:00:00.12Z"
+        );
+        assert_eq!(
+            ts(-1, 999_999_999).to_string(),
+            "1969-12-31T23:59:59.999999999Z"
+        );
+        assert_eq!(ts(-12_219_292_800, 0).to_string(), "1582-10-15T00:00:00Z");
+    }
+
+    #[test]
+    fn by_timestamp_orders_v1_by_time() {
+        // time_low wraps around between these two, so their byte order is
+        // the reverse of their creation order
+        let earlier = v1_at(1_700_000_421, 0);
+        let later = v1_at(1_700_000_422, 0);
+        assert!(earlier > later);
+        assert!(ByTimestamp(earlier) < ByTimestamp(later));
+        let v4: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert!(ByTimestamp(v4) < ByTimestamp(earlier));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn converts_to_chrono() {
+        let ts = v1_at(1_700_000_000, 500).timestamp().unwrap();
+        let dt = chrono::DateTime::<chrono::Utc>::try_from(ts).unwrap();
+        assert_eq!(dt.timestamp(), 1_700_000_000);
+        assert_eq!(dt.timestamp_subsec_nanos(), 500);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn converts_to_time() {
+        let ts = v1_at(1_700_000_000, 500).timestamp().unwrap();
+        let dt = time::OffsetDateTime::try_from(ts).unwrap();
+        assert_eq!(dt.unix_timestamp_nanos(), 1_700_000_000_000_000_500);
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn converts_to_jiff() {
+        let ts = v1_at(1_700_000_000, 500).timestamp().unwrap();
+        let dt = jiff::Timestamp::try_from(ts).unwrap();
+        assert_eq!(dt.as_nanosecond(), 1_700_000_000_000_000_500);
+    }
+}
@@ -0,0 +1,237 @@
+//! Custom v8 UUIDs made of caller-defined bit fields
+//!
+//! A v8 UUID has 122 bits that are free for any use; the other 6 hold the
+//! version and variant. This module treats those 122 bits as a single
+//! big-endian payload, so fields laid out earlier in the payload are more
+//! significant when IDs are compared or sorted.
+
+use uuid::{Uuid, Version};
+
+use crate::UuidB64;
+
+/// The number of bits a v8 UUID leaves free for custom data
+pub const V8_PAYLOAD_BITS: u8 = 122;
+
+/// A field in the custom payload of a v8 UUID
+///
+/// Fields are usually declared as constants, each following the previous
+/// one:
+///
+/// ```rust
+/// # use uuid_b64::{UuidB64, V8Builder, V8Field};
+/// const SHARD: V8Field = V8Field::new(0, 16);
+/// const WORKER: V8Field = SHARD.then(10);
+/// const MILLIS: V8Field = WORKER.then(48);
+///
+/// # #[cfg(feature = "rng")]
+/// # fn main() {
+/// let id = V8Builder::new()
+///     .with(SHARD, 42)
+///     .with(WORKER, 7)
+///     .with(MILLIS, 1_700_000_000_000)
+///     .random_fill()
+///     .build();
+///
+/// // the router only needs the string
+/// let parsed: UuidB64 = id.to_string().parse().unwrap();
+/// assert_eq!(SHARD.get(&parsed), Some(42));
+/// assert_eq!(WORKER.get(&parsed), Some(7));
+/// assert_eq!(MILLIS.get(&parsed), Some(1_700_000_000_000));
+/// # }
+/// # #[cfg(not(feature = "rng"))]
+/// # fn main() {}
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct V8Field {
+    offset: u8,
+    width: u8,
+}
+
+impl V8Field {
+    /// A field `width` bits wide, starting `offset` bits into the payload
+    ///
+    /// # Panics
+    ///
+    /// If `width` is zero or more than 64, or if the field does not fit in
+    /// the 122-bit payload. In a `const` this is a compile error.
+    pub const fn new(offset: u8, width: u8) -> V8Field {
+        assert!(
+            width > 0 && width <= 64,
+            "v8 fields must be 1 to 64 bits wide"
+        );
+        assert!(
+            offset as u16 + width as u16 <= V8_PAYLOAD_BITS as u16,
+            "v8 fields must fit in the 122 bit payload"
+        );
+        V8Field { offset, width }
+    }
+
+    /// A field `width` bits wide that starts right after this one
+    pub const fn then(self, width: u8) -> V8Field {
+        V8Field::new(self.offset + self.width, width)
+    }
+
+    /// How far into the payload this field starts, in bits
+    pub const fn offset(&self) -> u8 {
+        self.offset
+    }
+
+    /// How wide this field is, in bits
+    pub const fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// The largest value that fits in this field
+    pub const fn max_value(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
+    }
+
+    const fn shift(&self) -> u8 {
+        V8_PAYLOAD_BITS - self.offset - self.width
+    }
+
+    /// Read this field out of `id`, or `None` if `id` is not a v8 UUID
+    pub fn get<E>(&self, id: &UuidB64<E>) -> Option<u64> {
+        let payload = payload(id)?;
+        Some((payload >> self.shift()) as u64 & self.max_value())
+    }
+}
+
+/// Builds a v8 `UuidB64` out of [`V8Field`]s
+///
+/// Bits that are not covered by any field are zero, unless
+/// [`random_fill`](V8Builder::random_fill) is called.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct V8Builder {
+    payload: u128,
+    assigned: u128,
+    random_fill: bool,
+}
+
+impl V8Builder {
+    /// Start building a v8 UUID with an all-zero payload
+    pub fn new() -> V8Builder {
+        V8Builder::default()
+    }
+
+    /// Store `value` in `field`
+    ///
+    /// # Panics
+    ///
+    /// If `value` does not fit in `field`.
+    pub fn with(mut self, field: V8Field, value: u64) -> V8Builder {
+        assert!(
+            value <= field.max_value(),
+            "{} does not fit in a {} bit v8 field",
+            value,
+            field.width
+        );
+        let mask = u128::from(field.max_value()) << field.shift();
+        self.payload = (self.payload & !mask) | (u128::from(value) << field.shift());
+        self.assigned |= mask;
+        self
+    }
+
+    /// Fill every bit that no field was stored in with random data
+    ///
+    /// Needs the `rng` feature.
+    #[cfg(feature = "rng")]
+    pub fn random_fill(mut self) -> V8Builder {
+        self.random_fill = true;
+        self
+    }
+
+    /// Create the `UuidB64`
+    pub fn build(&self) -> UuidB64 {
+        #[cfg(feature = "rng")]
+        if self.random_fill {
+            let random = payload_from_uuid(Uuid::new_v4().as_u128());
+            return from_payload(self.payload | (random & !self.assigned));
+        }
+        from_payload(self.payload)
+    }
+}
+
+/// Build a v8 `UuidB64` from the low 122 bits of `payload`
+pub(crate) fn from_payload(payload: u128) -> UuidB64 {
+    UuidB64::from_uuid(Uuid::new_v8(uuid_from_payload(payload).to_be_bytes()))
+}
+
+/// The 122 free bits of `id`, or `None` if it is not a v8 UUID
+pub(crate) fn payload<E>(id: &UuidB64<E>) -> Option<u128> {
+    if id.uuid().get_version() != Some(Version::Custom) {
+        return None;
+    }
+    Some(payload_from_uuid(id.uuid().as_u128()))
+}
+
+/// Remove the version and variant bits, leaving the 122 free bits
+pub(crate) fn payload_from_uuid(uuid: u128) -> u128 {
+    let custom_a = uuid >> 80;
+    let custom_b = (uuid >> 64) & 0xfff;
+    let custom_c = uuid & ((1 << 62) - 1);
+    (custom_a << 74) | (custom_b << 62) | custom_c
+}
+
+/// Spread the 122 free bits out around the version and variant bits
+fn uuid_from_payload(payload: u128) -> u128 {
+    let custom_a = (payload >> 74) & ((1 << 48) - 1);
+    let custom_b = (payload >> 62) & 0xfff;
+    let custom_c = payload & ((1 << 62) - 1);
+    (custom_a << 80) | (custom_b << 64) | custom_c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHARD: V8Field = V8Field::new(0, 16);
+    const WORKER: V8Field = SHARD.then(10);
+    const MILLIS: V8Field = WORKER.then(48);
+    // straddles both the version and the variant bits
+    const WIDE: V8Field = V8Field::new(40, 64);
+
+    #[test]
+    fn fields_roundtrip() {
+        let builder = V8Builder::new()
+            .with(SHARD, 0xffff)
+            .with(WORKER, 0)
+            .with(MILLIS, 1_700_000_000_000);
+        #[cfg(feature = "rng")]
+        let builder = builder.random_fill();
+        let id = builder.build();
+        assert_eq!(id.uuid().get_version(), Some(Version::Custom));
+        assert_eq!(SHARD.get(&id), Some(0xffff));
+        assert_eq!(WORKER.get(&id), Some(0));
+        assert_eq!(MILLIS.get(&id), Some(1_700_000_000_000));
+
+        let id = V8Builder::new().with(WIDE, u64::MAX - 1).build();
+        assert_eq!(WIDE.get(&id), Some(u64::MAX - 1));
+        assert_eq!(id.uuid().get_variant(), uuid::Variant::RFC4122);
+    }
+
+    #[test]
+    fn earlier_fields_sort_first() {
+        let low = V8Builder::new().with(SHARD, 1).with(WORKER, 1023).build();
+        let high = V8Builder::new().with(SHARD, 2).with(WORKER, 0).build();
+        assert!(low < high);
+    }
+
+    #[test]
+    fn only_reads_v8() {
+        let v4: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(SHARD.get(&v4), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in a 10 bit v8 field")]
+    fn rejects_oversized_values() {
+        V8Builder::new().with(WORKER, 1024);
+    }
+
+    #[test]
+    #[should_panic(expected = "must fit in the 122 bit payload")]
+    fn rejects_oversized_layouts() {
+        V8Field::new(100, 23);
+    }
+}
@@ -0,0 +1,287 @@
+//! `UuidB64` wrappers that only hold a single UUID version
+//!
+//! The plain `UuidB64` accepts any 16 bytes. These wrappers additionally
+//! check, whenever they are parsed, deserialized or loaded from the database,
+//! that the embedded version matches and that the variant is the RFC 4122 one.
+
+use core::convert::TryFrom;
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use core::str::FromStr;
+
+use uuid::{Uuid, Variant};
+
+use crate::errors::ParseError;
+use crate::{UuidB64, UuidTimestamp};
+
+/// Check that `id` is an RFC 4122 UUID of the given version
+pub(crate) fn check_version(id: UuidB64, version: usize) -> Result<UuidB64, ParseError> {
+    if id.uuid().get_variant() != Variant::RFC4122 {
+        return Err(ParseError::WrongVariant);
+    }
+    let found = id.uuid().get_version_num();
+    if found != version {
+        return Err(ParseError::WrongVersion {
+            expected: version,
+            found,
+        });
+    }
+    Ok(id)
+}
+
+macro_rules! versioned_uuid_b64 {
+    ($(#[$meta:meta])* $name:ident, $version:expr) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[cfg_attr(
+            feature = "diesel-uuid",
+            derive(diesel::AsExpression, diesel::FromSqlRow)
+        )]
+        #[cfg_attr(feature = "diesel-uuid", diesel(sql_type = diesel::sql_types::Uuid))]
+        pub struct $name(UuidB64);
+
+        impl $name {
+            /// The UUID version this type holds
+            pub const VERSION: usize = $version;
+
+            /// Copy the unrestricted `UuidB64` out
+            pub fn uuid_b64(&self) -> UuidB64 {
+                self.0
+            }
+
+            /// Copy the raw UUID out
+            pub fn uuid(&self) -> Uuid {
+                self.0.uuid()
+            }
+        }
+
+        impl TryFrom<UuidB64> for $name {
+            type Error = ParseError;
+
+            fn try_from(id: UuidB64) -> Result<Self, Self::Error> {
+                check_version(id, $version).map($name)
+            }
+        }
+
+        impl TryFrom<Uuid> for $name {
+            type Error = ParseError;
+
+            fn try_from(id: Uuid) -> Result<Self, Self::Error> {
+                $name::try_from(UuidB64::from(id))
+            }
+        }
+
+        /// This also gives `UuidB64: From<Self>`
+        impl From<$name> for Uuid {
+            fn from(id: $name) -> Uuid {
+                id.0.uuid()
+            }
+        }
+
+        /// Parse a B64 encoded string, rejecting UUIDs of any other version
+        impl FromStr for $name {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $name::try_from(s.parse::<UuidB64>()?)
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                write!(f, "{}({})", stringify!($name), self.0)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        #[cfg(feature = "diesel-uuid")]
+        impl diesel::deserialize::FromSql<diesel::sql_types::Uuid, diesel::pg::Pg> for $name {
+            fn from_sql(value: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {
+                let id = <Uuid as diesel::deserialize::FromSql<
+                    diesel::sql_types::Uuid,
+                    diesel::pg::Pg,
+                >>::from_sql(value)?;
+                Ok($name::try_from(id)?)
+            }
+        }
+
+        #[cfg(feature = "diesel-uuid")]
+        impl diesel::serialize::ToSql<diesel::sql_types::Uuid, diesel::pg::Pg> for $name {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>,
+            ) -> diesel::serialize::Result {
+                use std::io::Write;
+
+                out.write_all(self.0 .0.as_bytes())?;
+                Ok(diesel::serialize::IsNull::No)
+            }
+        }
+    };
+}
+
+versioned_uuid_b64!(
+    /// A `UuidB64` that is guaranteed to be a v1 (time and node) UUID
+    UuidB64V1,
+    1
+);
+versioned_uuid_b64!(
+    /// A `UuidB64` that is guaranteed to be a v3 (MD5 name-based) UUID
+    UuidB64V3,
+    3
+);
+versioned_uuid_b64!(
+    /// A `UuidB64` that is guaranteed to be a v4 (random) UUID
+    ///
+    /// ```rust
+    /// # use uuid_b64::{UuidB64, UuidB64V4};
+    /// # #[cfg(all(feature = "std", feature = "rng"))]
+    /// # fn main() {
+    /// let id = UuidB64V4::new();
+    /// let parsed: UuidB64V4 = id.to_string().parse().unwrap();
+    /// assert_eq!(parsed, id);
+    ///
+    /// let time_ordered = UuidB64::new_v7().to_string();
+    /// assert!(time_ordered.parse::<UuidB64V4>().is_err());
+    /// # }
+    /// # #[cfg(not(all(feature = "std", feature = "rng")))]
+    /// # fn main() {}
+    /// ```
+    UuidB64V4,
+    4
+);
+versioned_uuid_b64!(
+    /// A `UuidB64` that is guaranteed to be a v5 (SHA-1 name-based) UUID
+    UuidB64V5,
+    5
+);
+versioned_uuid_b64!(
+    /// A `UuidB64` that is guaranteed to be a v6 (reordered time) UUID
+    UuidB64V6,
+    6
+);
+versioned_uuid_b64!(
+    /// A `UuidB64` that is guaranteed to be a v7 (Unix time-ordered) UUID
+    ///
+    /// ```rust
+    /// # use uuid_b64::{UuidB64, UuidB64V7};
+    /// # #[cfg(all(feature = "std", feature = "rng"))]
+    /// # fn main() {
+    /// let id = UuidB64V7::new();
+    /// let parsed: UuidB64V7 = id.to_string().parse().unwrap();
+    /// assert_eq!(parsed, id);
+    ///
+    /// let random = UuidB64::new().to_string();
+    /// assert!(random.parse::<UuidB64V7>().is_err());
+    /// # }
+    /// # #[cfg(not(all(feature = "std", feature = "rng")))]
+    /// # fn main() {}
+    /// ```
+    UuidB64V7,
+    7
+);
+versioned_uuid_b64!(
+    /// A `UuidB64` that is guaranteed to be a v8 (custom) UUID
+    UuidB64V8,
+    8
+);
+
+#[cfg(feature = "rng")]
+impl UuidB64V4 {
+    /// Generate a new v4 Uuid
+    pub fn new() -> UuidB64V4 {
+        UuidB64V4(UuidB64::new())
+    }
+}
+
+/// Construct a new V4 (random) UUID
+#[cfg(feature = "rng")]
+impl Default for UuidB64V4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UuidB64V3 {
+    /// Derive a v3 Uuid from a namespace and a name, see [`UuidB64::new_v3`]
+    pub fn new(namespace: &UuidB64, name: &[u8]) -> UuidB64V3 {
+        UuidB64V3(UuidB64::new_v3(namespace, name))
+    }
+}
+
+impl UuidB64V5 {
+    /// Derive a v5 Uuid from a namespace and a name, see [`UuidB64::new_v5`]
+    pub fn new(namespace: &UuidB64, name: &[u8]) -> UuidB64V5 {
+        UuidB64V5(UuidB64::new_v5(namespace, name))
+    }
+}
+
+macro_rules! timestamp_accessor {
+    ($($name:ident),*) => {$(
+        impl $name {
+            /// The creation time embedded in this ID
+            pub fn timestamp(&self) -> UuidTimestamp {
+                self.0
+                    .timestamp()
+                    .expect("time-based UUIDs always have a timestamp")
+            }
+        }
+    )*};
+}
+
+timestamp_accessor!(UuidB64V1, UuidB64V6, UuidB64V7);
+
+#[cfg(all(feature = "std", feature = "rng"))]
+impl UuidB64V7 {
+    /// Generate a new v7 Uuid, see [`UuidB64::new_v7`]
+    pub fn new() -> UuidB64V7 {
+        UuidB64V7(UuidB64::new_v7())
+    }
+}
+
+/// Construct a new V7 (time-ordered) UUID
+#[cfg(all(feature = "std", feature = "rng"))]
+impl Default for UuidB64V7 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_wrong_version() {
+        let v4 = "sMHuhm9GTxuNi3hJ51287g";
+        match v4.parse::<UuidB64V7>() {
+            Err(ParseError::WrongVersion {
+                expected: 7,
+                found: 4,
+            }) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(v4.parse::<UuidB64V4>().is_ok());
+    }
+
+    #[test]
+    fn rejects_wrong_variant() {
+        // version nibble says 4, but the variant bits are Microsoft's
+        let id = Uuid::from_bytes([0, 0, 0, 0, 0, 0, 0x40, 0, 0xc0, 0, 0, 0, 0, 0, 0, 0]);
+        match UuidB64V4::try_from(id) {
+            Err(ParseError::WrongVariant) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn displays_like_uuid_b64() {
+        let id = UuidB64V5::new(&UuidB64::NAMESPACE_DNS, b"rust-lang.org");
+        assert_eq!(id.to_string(), id.uuid_b64().to_string());
+        assert_eq!(format!("{:?}", id), format!("UuidB64V5({})", id));
+    }
+}